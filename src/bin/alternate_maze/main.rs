@@ -7,9 +7,16 @@ use ::rand::{rngs::SmallRng, SeedableRng};
 use ::thunder_book_game_search::{
     game::alternate::{AlternateGameState, WinningStatus},
     search::alternate::{
-        alpha_beta::AlphaBeta, iterative_deepening_alpha_beta::IterativeDeepeningAlphaBeta,
-        mcts::MCTS, mini_max::MiniMax, primitive_montecarlo::PrimitiveMontecarlo, random::Random,
-        thuder::Thunder, ChooseAction,
+        alpha_beta::AlphaBeta,
+        epsilon_greedy::EpsilonGreedy,
+        iterative_deepening_alpha_beta::IterativeDeepeningAlphaBeta,
+        mcts::MCTS,
+        mini_max::MiniMax,
+        primitive_montecarlo::PrimitiveMontecarlo,
+        puct::{UniformPolicy, PUCT},
+        random::Random,
+        thuder::Thunder,
+        ChooseAction,
     },
 };
 
@@ -104,7 +111,11 @@ fn main() {
     let long_primitive_montecarlo = PrimitiveMontecarlo::new(Duration::from_micros(1000));
     let short_mcts = MCTS::new(Duration::from_micros(10));
     let long_mcts = MCTS::new(Duration::from_micros(1000));
+    let epsilon_greedy_mcts =
+        MCTS::with_playout(Duration::from_micros(1000), EpsilonGreedy::new(0.3), None);
+    let depth_limited_mcts = MCTS::with_playout(Duration::from_micros(1000), Random {}, Some(10));
     let thunder = Thunder::new(Duration::from_micros(1000));
+    let puct = PUCT::new(UniformPolicy {}, 1.0, Duration::from_micros(1000));
 
     println!("random vs. mini_max");
     play(&random, &mini_max, games, h, w, end_turn, seed);
@@ -175,4 +186,21 @@ fn main() {
         end_turn,
         seed,
     );
+
+    println!("thunder vs. puct");
+    play(&thunder, &puct, games, h, w, end_turn, seed);
+
+    println!("[mcts] random playout vs. epsilon greedy playout");
+    play(
+        &long_mcts,
+        &epsilon_greedy_mcts,
+        games,
+        h,
+        w,
+        end_turn,
+        seed,
+    );
+
+    println!("[mcts] random playout vs. depth limited playout");
+    play(&long_mcts, &depth_limited_mcts, games, h, w, end_turn, seed);
 }
@@ -23,7 +23,7 @@ impl Character {
     }
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub enum Action {
     Right,
     Left,
@@ -32,6 +32,8 @@ pub enum Action {
 }
 
 impl Action {
+    const ALL: [Action; 4] = [Action::Right, Action::Left, Action::Down, Action::Up];
+
     fn dydx(&self) -> (isize, isize) {
         match self {
             Action::Right => (0, 1),
@@ -87,24 +89,40 @@ impl AlternateMazeState {
     }
 }
 
+impl AlternateMazeState {
+    fn movable(&self, action: Action) -> bool {
+        let (dy, dx) = action.dydx();
+        match (
+            self.characters[0].y.checked_add_signed(dy),
+            self.characters[0].x.checked_add_signed(dx),
+        ) {
+            (Some(ty), Some(tx)) => ty < self.point.len() && tx < self.point[ty].len(),
+            _ => false,
+        }
+    }
+}
+
 impl AlternateGameState for AlternateMazeState {
     type Action = Action;
 
     fn legal_actions(&self) -> Vec<Action> {
-        let mut actions = Vec::new();
-        for action in [Action::Right, Action::Left, Action::Down, Action::Up] {
-            let (dy, dx) = action.dydx();
-            match (
-                self.characters[0].y.checked_add_signed(dy),
-                self.characters[0].x.checked_add_signed(dx),
-            ) {
-                (Some(ty), Some(tx)) if ty < self.point.len() && tx < self.point[ty].len() => {
-                    actions.push(action);
-                }
-                _ => {}
+        Action::ALL
+            .into_iter()
+            .filter(|&action| self.movable(action))
+            .collect()
+    }
+
+    fn random_action<R>(&self, rng: &mut R) -> Action
+    where
+        R: Rng + ?Sized,
+    {
+        // 盤面の外に出ない手が出るまで引き直す
+        loop {
+            let action = Action::ALL[rng.gen_range(0..Action::ALL.len())];
+            if self.movable(action) {
+                return action;
             }
         }
-        actions
     }
 
     fn advance(&mut self, action: Action) {
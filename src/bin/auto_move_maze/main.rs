@@ -1,18 +1,28 @@
 mod state;
 
+use ::std::time::Duration;
+
 use ::rand::{rngs::SmallRng, SeedableRng};
 
 use ::thunder_book_game_search::{
     game::heuristic::HeuristicGameState,
     search::heuristic::{
-        hill_climb::HillClimb, random::Random, simulated_annealing::SimulatedAnnealing, ChooseState,
+        genetic::GeneticAlgorithm,
+        great_deluge::GreatDeluge,
+        hill_climb::{HillClimb, Restart},
+        late_acceptance::LateAcceptance,
+        parallel_tempering::ParallelTempering,
+        random::Random,
+        simulated_annealing::{Schedule, SimulatedAnnealing},
+        tabu::TabuSearch,
+        ChooseState,
     },
 };
 
 use crate::state::AutoMoveMazeState;
 
 fn average_score<T>(
-    best_state: T,
+    best_state: &T,
     games: u32,
     h: usize,
     w: usize,
@@ -33,27 +43,153 @@ where
     f64::from(total) / f64::from(games)
 }
 
+// choose_incremental() のように ChooseState を実装していない探索のため
+fn average_score_by<F>(
+    choose: F,
+    games: u32,
+    h: usize,
+    w: usize,
+    end_turn: u32,
+    character_num: usize,
+    seed: u64,
+) -> f64
+where
+    F: Fn(&AutoMoveMazeState) -> AutoMoveMazeState,
+{
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let mut total = 0;
+    for _ in 0..games {
+        let state = AutoMoveMazeState::new(h, w, end_turn, character_num, &mut rng);
+        let score = choose(&state).start();
+        total += score;
+    }
+    f64::from(total) / f64::from(games)
+}
+
 fn main() {
     let (games, h, w, end_turn, character_num, seed) = (1, 5, 5, 5, 3, 31415);
 
     let random = Random {};
     let hill_climb = HillClimb::new(10_000);
+    // 合計の遷移回数は hill_climb と同じ
+    let multi_start = HillClimb::new(1_000).with_restarts(9, Restart::Initialize);
+    let iterated_local_search = HillClimb::new(1_000).with_restarts(9, Restart::Perturb);
     let simulated_annealing = SimulatedAnnealing::new(10_000, 500.0, 10.0);
+    let simulated_annealing_timed =
+        SimulatedAnnealing::with_time_limit(Duration::from_millis(100), 500.0, 10.0)
+            .with_schedule(Schedule::Exponential)
+            .with_reheat(3);
+    let tabu = TabuSearch::new(1_000, 10, 5);
+    let genetic = GeneticAlgorithm::new(50, 200, 0.2);
+    // 4 レプリカで合計 10_000 回遷移する
+    let parallel_tempering = ParallelTempering::new(4, 10.0, 500.0, 25, 100);
+    let late_acceptance = LateAcceptance::new(10_000, 50);
+    let great_deluge = GreatDeluge::new(10_000, 0.01);
 
     println!(
         "random: {}",
-        average_score(random, games, h, w, end_turn, character_num, seed)
+        average_score(&random, games, h, w, end_turn, character_num, seed)
     );
 
     println!(
         "hill climb: {}",
-        average_score(hill_climb, games, h, w, end_turn, character_num, seed)
+        average_score(&hill_climb, games, h, w, end_turn, character_num, seed)
     );
 
     println!(
         "simulated annealing: {}",
         average_score(
-            simulated_annealing,
+            &simulated_annealing,
+            games,
+            h,
+            w,
+            end_turn,
+            character_num,
+            seed
+        )
+    );
+
+    println!(
+        "hill climb (incremental): {}",
+        average_score_by(
+            |state| hill_climb.choose_incremental(state).0,
+            games,
+            h,
+            w,
+            end_turn,
+            character_num,
+            seed
+        )
+    );
+
+    println!(
+        "simulated annealing (incremental): {}",
+        average_score_by(
+            |state| simulated_annealing.choose_incremental(state).0,
+            games,
+            h,
+            w,
+            end_turn,
+            character_num,
+            seed
+        )
+    );
+
+    println!(
+        "simulated annealing (100ms, exponential, 3 cycles): {}",
+        average_score(
+            &simulated_annealing_timed,
+            games,
+            h,
+            w,
+            end_turn,
+            character_num,
+            seed
+        )
+    );
+
+    println!(
+        "tabu search: {}",
+        average_score(&tabu, games, h, w, end_turn, character_num, seed)
+    );
+
+    println!(
+        "genetic algorithm: {}",
+        average_score(&genetic, games, h, w, end_turn, character_num, seed)
+    );
+
+    println!(
+        "parallel tempering: {}",
+        average_score(
+            &parallel_tempering,
+            games,
+            h,
+            w,
+            end_turn,
+            character_num,
+            seed
+        )
+    );
+
+    println!(
+        "late acceptance: {}",
+        average_score(&late_acceptance, games, h, w, end_turn, character_num, seed)
+    );
+
+    println!(
+        "great deluge: {}",
+        average_score(&great_deluge, games, h, w, end_turn, character_num, seed)
+    );
+
+    println!(
+        "hill climb (multi-start): {}",
+        average_score(&multi_start, games, h, w, end_turn, character_num, seed)
+    );
+
+    println!(
+        "hill climb (iterated local search): {}",
+        average_score(
+            &iterated_local_search,
             games,
             h,
             w,
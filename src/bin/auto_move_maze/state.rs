@@ -1,13 +1,12 @@
 // オート数字集め迷路
 
-use ::std::{
-    cmp::Reverse,
-    fmt::{self, Formatter},
-};
+use ::std::fmt::{self, Formatter};
 
 use ::rand::{thread_rng, Rng};
 
-use ::thunder_book_game_search::game::heuristic::HeuristicGameState;
+use ::thunder_book_game_search::game::heuristic::{
+    HeuristicGameState, IncrementalHeuristicGameState,
+};
 
 #[derive(Clone)]
 struct Coord {
@@ -28,6 +27,23 @@ pub struct AutoMoveMazeState {
     turn: u32,
     end_turn: u32,
     game_score: u32,
+    // 今の初期位置での start()。delta() で差を取るために覚えておく
+    // 初期位置が変わったら None にする
+    score: Option<u32>,
+    // 直前に delta() した移動と、移ったあとの start()
+    // すぐに apply() されたら計算しなおさずに使う
+    last_delta: Option<(Move, u32)>,
+    // delta() でゲームを進めるための作業領域
+    work_characters: Vec<Coord>,
+    work_point: Vec<Vec<u8>>,
+}
+
+// characters[index] の初期位置を (y, x) にする
+#[derive(Clone, PartialEq)]
+pub struct Move {
+    index: usize,
+    y: usize,
+    x: usize,
 }
 
 impl AutoMoveMazeState {
@@ -52,75 +68,175 @@ impl AutoMoveMazeState {
             turn: 0,
             end_turn,
             game_score: 0,
+            score: None,
+            last_delta: None,
+            work_characters: Vec::new(),
+            work_point: Vec::new(),
         };
 
         state.initialize();
         state
     }
 
-    fn done(&self) -> bool {
-        self.turn == self.end_turn
+    // delta() で作業領域を使ってゲームを最後まで進める
+    fn simulate(&mut self) -> u32 {
+        self.work_point.clone_from(&self.point);
+        self.game_score
+            + play(
+                &mut self.work_characters,
+                &mut self.work_point,
+                self.end_turn - self.turn,
+            )
     }
+}
 
-    fn advance(&mut self) {
-        for c in &mut self.characters {
-            let mut next = Vec::new();
-            // 右
-            if c.x + 1 < self.point[c.y].len() {
-                next.push(((c.y, c.x + 1), self.point[c.y][c.x + 1]));
-            }
-            // 左
-            if c.x > 0 {
-                next.push(((c.y, c.x - 1), self.point[c.y][c.x - 1]));
-            }
-            // 下
-            if c.y + 1 < self.point.len() {
-                next.push(((c.y + 1, c.x), self.point[c.y + 1][c.x]));
-            }
-            // 上
-            if c.y > 0 {
-                next.push(((c.y - 1, c.x), self.point[c.y - 1][c.x]));
+// turns ターン進めて、取ったポイントの合計を返す
+fn play(characters: &mut [Coord], point: &mut [Vec<u8>], turns: u32) -> u32 {
+    // キャラクターの初期位置にあるポイントは無効
+    for c in characters.iter() {
+        point[c.y][c.x] = 0;
+    }
+    let mut score = 0;
+    for _ in 0..turns {
+        score += step(characters, point);
+    }
+    score
+}
+
+// 全キャラクターを1マスずつ動かして、取ったポイントの合計を返す
+fn step(characters: &mut [Coord], point: &mut [Vec<u8>]) -> u32 {
+    let mut score = 0;
+    for c in characters {
+        // 同点なら右, 左, 下, 上の順に優先する
+        let mut next = None;
+        let mut update = |y: usize, x: usize| {
+            if next.map_or(true, |(_, p)| p < point[y][x]) {
+                next = Some(((y, x), point[y][x]));
             }
-            assert!(!next.is_empty());
-            next.sort_by_key(|(_, p)| Reverse(*p)); // stable sort
-            let ((y, x), point) = next[0];
-            c.y = y;
-            c.x = x;
-            self.game_score += u32::from(point);
-            self.point[y][x] = 0;
+        };
+        // 右
+        if c.x + 1 < point[c.y].len() {
+            update(c.y, c.x + 1);
+        }
+        // 左
+        if c.x > 0 {
+            update(c.y, c.x - 1);
+        }
+        // 下
+        if c.y + 1 < point.len() {
+            update(c.y + 1, c.x);
         }
-        self.turn += 1;
+        // 上
+        if c.y > 0 {
+            update(c.y - 1, c.x);
+        }
+        let ((y, x), p) = next.unwrap();
+        c.y = y;
+        c.x = x;
+        score += u32::from(p);
+        point[y][x] = 0;
     }
+    score
 }
 
 impl HeuristicGameState for AutoMoveMazeState {
+    type Objective = u32;
+
     fn initialize(&mut self) {
         let mut rng = thread_rng();
         for c in &mut self.characters {
             c.y = rng.gen_range(0..self.point.len());
             c.x = rng.gen_range(0..self.point[c.y].len());
         }
+        self.score = None;
+        self.last_delta = None;
     }
 
     fn start(&self) -> u32 {
-        let mut state = self.clone();
-        // キャラクターの初期位置にあるポイントは無効
-        for c in &state.characters {
-            state.point[c.y][c.x] = 0;
+        let mut characters = self.characters.clone();
+        let mut point = self.point.clone();
+        self.game_score + play(&mut characters, &mut point, self.end_turn - self.turn)
+    }
+
+    fn transition(&mut self) {
+        let mv = self.propose();
+        self.characters[mv.index] = Coord::new(mv.y, mv.x);
+        self.score = None;
+        self.last_delta = None;
+    }
+
+    // 半分くらいのキャラクターの初期位置を選びなおす
+    fn perturb(&mut self) {
+        let mut rng = thread_rng();
+        for c in &mut self.characters {
+            if rng.gen_bool(0.5) {
+                c.y = rng.gen_range(0..self.point.len());
+                c.x = rng.gen_range(0..self.point[c.y].len());
+            }
         }
-        while !state.done() {
-            state.advance();
+        self.score = None;
+        self.last_delta = None;
+    }
+
+    // キャラクターごとにどちらかの親の初期位置を受け継ぐ
+    fn crossover(&self, other: &Self) -> Self {
+        let mut rng = thread_rng();
+        let mut child = self.clone();
+        for (c, o) in child.characters.iter_mut().zip(&other.characters) {
+            if rng.gen_bool(0.5) {
+                c.clone_from(o);
+            }
         }
-        state.game_score
+        child.score = None;
+        child.last_delta = None;
+        child
     }
+}
 
-    fn transition(&mut self) {
+impl IncrementalHeuristicGameState for AutoMoveMazeState {
+    type Move = Move;
+
+    fn propose(&self) -> Move {
         let mut rng = thread_rng();
-        let i = rng.gen_range(0..self.characters.len());
-        let new_y = rng.gen_range(0..self.point.len());
-        let new_x = rng.gen_range(0..self.point[new_y].len());
-        self.characters[i].y = new_y;
-        self.characters[i].x = new_x;
+        let index = rng.gen_range(0..self.characters.len());
+        let y = rng.gen_range(0..self.point.len());
+        let x = rng.gen_range(0..self.point[y].len());
+        Move { index, y, x }
+    }
+
+    // キャラクターどうしが影響しあうので最後まで進めなおすが、clone() はしない
+    fn delta(&mut self, mv: &Move) -> f64 {
+        let score = match self.score {
+            Some(score) => score,
+            None => {
+                self.work_characters.clone_from(&self.characters);
+                let score = self.simulate();
+                self.score = Some(score);
+                score
+            }
+        };
+        self.work_characters.clone_from(&self.characters);
+        self.work_characters[mv.index] = Coord::new(mv.y, mv.x);
+        let next_score = self.simulate();
+        self.last_delta = Some((mv.clone(), next_score));
+        f64::from(next_score) - f64::from(score)
+    }
+
+    fn apply(&mut self, mv: Move) {
+        self.characters[mv.index] = Coord::new(mv.y, mv.x);
+        self.score = match self.last_delta.take() {
+            Some((last, next_score)) if last == mv => Some(next_score),
+            _ => None,
+        };
+    }
+
+    fn reverse_move(&self, mv: &Move) -> Move {
+        let c = &self.characters[mv.index];
+        Move {
+            index: mv.index,
+            y: c.y,
+            x: c.x,
+        }
     }
 }
 
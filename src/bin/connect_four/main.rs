@@ -90,4 +90,10 @@ fn main() {
 
     println!("random vs. mcts");
     play(&random, &mcts, games, h, w);
+
+    let (_, stats) = mcts.choose_with_stats(&ConnectFourState::new(h, w));
+    println!(
+        "mcts (first move): nodes = {}, playouts = {}, max_depth = {}",
+        stats.nodes, stats.playouts, stats.max_depth
+    );
 }
@@ -1,8 +1,10 @@
 use ::std::{fmt, mem};
 
+use ::rand::Rng;
+
 use ::thunder_book_game_search::game::alternate::{AlternateGameState, WinningStatus};
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct DropPiece {
     x: usize,
 }
@@ -36,20 +38,39 @@ impl ConnectFourState {
     }
 }
 
+impl ConnectFourState {
+    // 一番上が空いていればx列目に駒を落とせる
+    fn droppable(&self, x: usize) -> bool {
+        !self.my_board[self.h - 1][x] && !self.enemy_board[self.h - 1][x]
+    }
+}
+
 impl AlternateGameState for ConnectFourState {
     type Action = DropPiece;
 
     fn legal_actions(&self) -> Vec<Self::Action> {
         let mut actions = Vec::new();
         for x in 0..self.w {
-            // 一番上が空いていればx列目に駒を落とせる
-            if !self.my_board[self.h - 1][x] && !self.enemy_board[self.h - 1][x] {
+            if self.droppable(x) {
                 actions.push(DropPiece::new(x));
             }
         }
         actions
     }
 
+    fn random_action<R>(&self, rng: &mut R) -> Self::Action
+    where
+        R: Rng + ?Sized,
+    {
+        // 埋まっていない列が出るまで引き直す
+        loop {
+            let x = rng.gen_range(0..self.w);
+            if self.droppable(x) {
+                return DropPiece::new(x);
+            }
+        }
+    }
+
     fn advance(&mut self, action: Self::Action) {
         assert!(!self.done());
 
@@ -101,7 +122,7 @@ impl AlternateGameState for ConnectFourState {
         if yoko >= 4 || naname >= 4 || menana >= 4 || tate >= 4 {
             // 今回駒が揃ったので次に打つ側の負け
             self.winning_status_cache = Some(WinningStatus::Lose);
-        } else if self.legal_actions().is_empty() {
+        } else if (0..self.w).all(|x| !self.droppable(x)) {
             self.winning_status_cache = Some(WinningStatus::Draw);
         }
     }
@@ -1,13 +1,15 @@
 mod state;
 
-use ::std::time::Duration;
+use ::std::{thread, time::Duration};
 
 use ::rand::{rngs::SmallRng, SeedableRng};
 
 use ::thunder_book_game_search::{
     game::one_player::OnePlayerGameState,
     search::one_player::{
-        beam::Beam, chokudai::Chokudai, greedy::Greedy, random::Random, ChooseAction,
+        astar::AStar, beam::Beam, chokudai::Chokudai, exhaustive::Exhaustive, greedy::Greedy,
+        nmcs::NestedMonteCarlo, nrpa::NRPA, parallel_beam::ParallelBeam, random::Random,
+        sp_mcts::SPMCTS, tree_beam::TreeBeam, ChooseAction, PlanActions,
     },
 };
 
@@ -30,6 +32,33 @@ where
     f64::from(total) / f64::from(games)
 }
 
+// 毎ターン探索せずに、探索で得た手順を最後まで再生する
+fn average_score_by_plan<T>(
+    planner: T,
+    games: u32,
+    h: usize,
+    w: usize,
+    end_turn: u32,
+    seed: u64,
+) -> f64
+where
+    T: PlanActions<MazeState>,
+{
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let mut total = 0;
+    for _ in 0..games {
+        let mut state = MazeState::new(h, w, end_turn, &mut rng);
+        while !state.done() {
+            // 時間切れで手順が途中までしかなければ、その続きから探索しなおす
+            for action in planner.plan(&state) {
+                state.advance(action);
+            }
+        }
+        total += state.evaluate_score();
+    }
+    f64::from(total) / f64::from(games)
+}
+
 fn main() {
     let (games, h, w, end_turn, seed) = (20, 30, 30, 100, 9876543210);
 
@@ -52,6 +81,17 @@ fn main() {
             seed
         )
     );
+    println!(
+        "beam (dedup): {}",
+        average_score(
+            Beam::new(5, Duration::from_millis(10)).with_dedup(),
+            games,
+            h,
+            w,
+            end_turn,
+            seed
+        )
+    );
     println!(
         "chokudai: {}",
         average_score(
@@ -63,4 +103,139 @@ fn main() {
             seed
         )
     );
+    println!(
+        "beam (plan once): {}",
+        average_score_by_plan(
+            Beam::new(1000, Duration::from_millis(1000)),
+            games,
+            h,
+            w,
+            end_turn,
+            seed
+        )
+    );
+    println!(
+        "beam (plan once, diverse): {}",
+        average_score_by_plan(
+            Beam::new(1000, Duration::from_millis(1000)).with_diversity(5),
+            games,
+            h,
+            w,
+            end_turn,
+            seed
+        )
+    );
+    println!(
+        "tree beam (plan once): {}",
+        average_score_by_plan(
+            TreeBeam::new(1000, Duration::from_millis(1000)),
+            games,
+            h,
+            w,
+            end_turn,
+            seed
+        )
+    );
+    let threads = thread::available_parallelism().map_or(1, |n| n.get());
+    println!(
+        "parallel beam (plan once, {} threads): {}",
+        threads,
+        average_score_by_plan(
+            ParallelBeam::new(1000, threads, Duration::from_millis(1000)),
+            games,
+            h,
+            w,
+            end_turn,
+            seed
+        )
+    );
+    println!(
+        "chokudai (plan once): {}",
+        average_score_by_plan(
+            Chokudai::new(5, end_turn as usize, Duration::from_millis(1000)),
+            games,
+            h,
+            w,
+            end_turn,
+            seed
+        )
+    );
+    println!(
+        "chokudai (plan once, widening): {}",
+        average_score_by_plan(
+            Chokudai::new(1, end_turn as usize, Duration::from_millis(1000)).with_widening(1, 20),
+            games,
+            h,
+            w,
+            end_turn,
+            seed
+        )
+    );
+
+    println!(
+        "nmcs (plan once, level 2): {}",
+        average_score_by_plan(NestedMonteCarlo::new(2), games, h, w, end_turn, seed)
+    );
+    println!(
+        "nrpa (plan once, level 2, 100 iterations): {}",
+        average_score_by_plan(NRPA::new(2, 100), games, h, w, end_turn, seed)
+    );
+    println!(
+        "sp-mcts (plan once): {}",
+        average_score_by_plan(
+            SPMCTS::new(Duration::from_millis(1000)),
+            games,
+            h,
+            w,
+            end_turn,
+            seed
+        )
+    );
+    // 小さい盤面で最適解と比べる
+    let (h, w, end_turn) = (5, 5, 10);
+    println!(
+        "random ({h}x{w}, {end_turn} turns): {}",
+        average_score(Random {}, games, h, w, end_turn, seed)
+    );
+    println!(
+        "greedy ({h}x{w}, {end_turn} turns): {}",
+        average_score(Greedy {}, games, h, w, end_turn, seed)
+    );
+    println!(
+        "beam ({h}x{w}, {end_turn} turns): {}",
+        average_score(
+            Beam::new(5, Duration::from_millis(10)),
+            games,
+            h,
+            w,
+            end_turn,
+            seed
+        )
+    );
+    println!(
+        "chokudai ({h}x{w}, {end_turn} turns): {}",
+        average_score(
+            Chokudai::new(1, end_turn as usize, Duration::from_millis(10)),
+            games,
+            h,
+            w,
+            end_turn,
+            seed
+        )
+    );
+    println!(
+        "a star ({h}x{w}, {end_turn} turns, optimal): {}",
+        average_score_by_plan(
+            AStar::new(Duration::from_secs(10)).with_dedup(),
+            games,
+            h,
+            w,
+            end_turn,
+            seed
+        )
+    );
+    println!(
+        "exhaustive ({h}x{w}, {end_turn} turns, optimal): {}",
+        average_score_by_plan(Exhaustive {}, games, h, w, end_turn, seed)
+    );
 }
@@ -1,15 +1,16 @@
 // 数字集め迷路
 
 use ::std::{
-    cmp,
+    collections::hash_map::DefaultHasher,
     fmt::{self, Formatter},
+    hash::{Hash, Hasher},
 };
 
 use ::rand::Rng;
 
 use ::thunder_book_game_search::game::one_player::OnePlayerGameState;
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Coord {
     y: usize,
     x: usize,
@@ -21,7 +22,7 @@ impl Coord {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, PartialEq, Eq)]
 pub enum Action {
     Right,
     Left,
@@ -40,13 +41,15 @@ impl Action {
     }
 }
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(PartialEq, Eq)]
 pub struct MazeState {
     character: Coord,
     point: Vec<Vec<u8>>,
     turn: u32,
     end_turn: u32,
     game_score: u32,
+    // undo() のために各ターンで取ったポイントを積んでおく
+    history: Vec<u8>,
 }
 
 impl MazeState {
@@ -69,10 +72,34 @@ impl MazeState {
             turn: 0,
             end_turn,
             game_score: 0,
+            history: Vec::new(),
         }
     }
 }
 
+impl Clone for MazeState {
+    fn clone(&self) -> Self {
+        Self {
+            character: self.character.clone(),
+            point: self.point.clone(),
+            turn: self.turn,
+            end_turn: self.end_turn,
+            game_score: self.game_score,
+            history: self.history.clone(),
+        }
+    }
+
+    // ビームサーチで捨てた状態に上書きするときにメモリを確保しなおさない
+    fn clone_from(&mut self, source: &Self) {
+        self.character.clone_from(&source.character);
+        self.point.clone_from(&source.point);
+        self.turn = source.turn;
+        self.end_turn = source.end_turn;
+        self.game_score = source.game_score;
+        self.history.clone_from(&source.history);
+    }
+}
+
 impl OnePlayerGameState for MazeState {
     type Action = Action;
 
@@ -101,10 +128,22 @@ impl OnePlayerGameState for MazeState {
         character.y = character.y.checked_add_signed(dy).unwrap();
         character.x = character.x.checked_add_signed(dx).unwrap();
         self.game_score += u32::from(self.point[character.y][character.x]);
+        self.history.push(self.point[character.y][character.x]);
         self.point[character.y][character.x] = 0;
         self.turn += 1;
     }
 
+    fn undo(&mut self, action: Action) {
+        let point = self.history.pop().unwrap();
+        let character = &mut self.character;
+        self.point[character.y][character.x] = point;
+        self.game_score -= u32::from(point);
+        let (dy, dx) = action.dydx();
+        character.y = character.y.checked_add_signed(-dy).unwrap();
+        character.x = character.x.checked_add_signed(-dx).unwrap();
+        self.turn -= 1;
+    }
+
     fn done(&self) -> bool {
         self.turn == self.end_turn
     }
@@ -112,6 +151,42 @@ impl OnePlayerGameState for MazeState {
     fn evaluate_score(&self) -> u32 {
         self.game_score
     }
+
+    // 1ターンに1マスしか取れないので、残りターン数以内で行けるマスのポイントを
+    // 大きいほうから残りターン数個取れたとして見積もる
+    fn upper_bound(&self) -> u32 {
+        let rest = (self.end_turn - self.turn) as usize;
+        let mut points = Vec::new();
+        for y in 0..self.point.len() {
+            for x in 0..self.point[y].len() {
+                if self.point[y][x] > 0
+                    && self.character.y.abs_diff(y) + self.character.x.abs_diff(x) <= rest
+                {
+                    points.push(self.point[y][x]);
+                }
+            }
+        }
+        points.sort_unstable_by(|a, b| b.cmp(a));
+        self.game_score + points.iter().take(rest).map(|&p| u32::from(p)).sum::<u32>()
+    }
+
+    fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        // history は経路によって違うので含めない
+        (&self.character, &self.point, self.turn).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // 同じマスから同じ向きに動くことを同じ手とみなす
+    fn action_key(&self, action: Action) -> u64 {
+        let position = self.character.y * self.point[0].len() + self.character.x;
+        (position * 4 + action as usize) as u64
+    }
+
+    // キャラクターの位置が同じなら似た状態とみなす
+    fn diversity_key(&self) -> u64 {
+        (self.character.y * self.point[0].len() + self.character.x) as u64
+    }
 }
 
 impl fmt::Debug for MazeState {
@@ -135,15 +210,3 @@ impl fmt::Debug for MazeState {
         Ok(())
     }
 }
-
-impl cmp::Ord for MazeState {
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
-        self.evaluate_score().cmp(&other.evaluate_score())
-    }
-}
-
-impl cmp::PartialOrd for MazeState {
-    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
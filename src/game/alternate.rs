@@ -1,6 +1,15 @@
+use ::rand::{seq::SliceRandom, Rng};
+
 pub trait AlternateGameState: Clone {
     type Action: Clone + Copy;
     fn legal_actions(&self) -> Vec<Self::Action>;
+    // プレイアウトを速くするために、legal_actions() を作らずに合法手をランダムに選べるなら実装する
+    fn random_action<R>(&self, rng: &mut R) -> Self::Action
+    where
+        R: Rng + ?Sized,
+    {
+        self.legal_actions().choose(rng).copied().unwrap()
+    }
     fn advance(&mut self, action: Self::Action);
     fn done(&self) -> bool;
     fn score(&self) -> i16 {
@@ -1,8 +1,103 @@
+use ::std::cmp::Ordering;
+
+// start() が返す値
+pub trait Objective: Copy + PartialOrd {
+    fn to_f64(self) -> f64;
+}
+
+impl Objective for u32 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+}
+
+impl Objective for i32 {
+    fn to_f64(self) -> f64 {
+        f64::from(self)
+    }
+}
+
+impl Objective for i64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Objective for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+// start() を大きくしたいか小さくしたいか
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Maximize,
+    Minimize,
+}
+
+impl Direction {
+    // a のほうがよければ Greater
+    // 比べられない値 (NaN) は同じとみなす
+    pub fn compare<T>(self, a: T, b: T) -> Ordering
+    where
+        T: Objective,
+    {
+        let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+        match self {
+            Direction::Maximize => ordering,
+            Direction::Minimize => ordering.reverse(),
+        }
+    }
+
+    // a のほうが真によいか
+    pub fn better<T>(self, a: T, b: T) -> bool
+    where
+        T: Objective,
+    {
+        self.compare(a, b) == Ordering::Greater
+    }
+
+    // start() の増分を、よくなると正になるように直す
+    pub fn gain(self, delta: f64) -> f64 {
+        match self {
+            Direction::Maximize => delta,
+            Direction::Minimize => -delta,
+        }
+    }
+}
+
 pub trait HeuristicGameState: Clone {
+    type Objective: Objective;
+    const DIRECTION: Direction = Direction::Maximize;
     // 初期解を生成する
     fn initialize(&mut self);
     // ゲームを最後まで進めてスコアを返す
-    fn start(&self) -> u32;
+    fn start(&self) -> Self::Objective;
     // 近傍へ移る
     fn transition(&mut self);
+    // 局所最適から抜け出すために transition() より大きく動かす
+    fn perturb(&mut self) {
+        unimplemented!("反復局所探索のために実装する")
+    }
+    // self と other を親とする子を作る
+    fn crossover(&self, _other: &Self) -> Self {
+        unimplemented!("遺伝的アルゴリズムのために実装する")
+    }
+}
+
+// 状態を clone() して start() しなおさずに近傍を評価するための差分 API
+pub trait IncrementalHeuristicGameState: HeuristicGameState {
+    type Move;
+    // 近傍への移動をランダムに1つ作る。状態は変えない
+    fn propose(&self) -> Self::Move;
+    // mv で移ったときの start() の増分。DIRECTION によらず、移ったあと - 今
+    // 作業領域を使い回せるように &mut self を取るが、状態は変えない
+    fn delta(&mut self, mv: &Self::Move) -> f64;
+    // mv で近傍へ移る
+    fn apply(&mut self, mv: Self::Move);
+    // mv で移ったあとに、今の状態へ戻る移動
+    fn reverse_move(&self, _mv: &Self::Move) -> Self::Move {
+        unimplemented!("タブーサーチのために実装する")
+    }
 }
@@ -2,6 +2,33 @@ pub trait OnePlayerGameState: Clone {
     type Action: Clone + Copy;
     fn legal_actions(&self) -> Vec<Self::Action>;
     fn advance(&mut self, action: Self::Action);
+    // advance(action) する前の状態に戻す
+    fn undo(&mut self, _action: Self::Action) {
+        unimplemented!("差分更新ビームサーチのために実装する")
+    }
     fn done(&self) -> bool;
     fn evaluate_score(&self) -> u32;
+    // ビームサーチなどで状態を並べるための評価値
+    // ゲームのスコアとは別の評価をしたいときに実装する
+    fn heuristic_score(&self) -> i64 {
+        i64::from(self.evaluate_score())
+    }
+    fn state_hash(&self) -> u64 {
+        unimplemented!("重複除去つきのビームサーチなどのために実装する")
+    }
+    // ここから終了までに到達できる evaluate_score() の上界
+    // 大きめに見積もるのはよいが、実際に到達できる値より小さくしてはいけない
+    // 終了状態では evaluate_score() と等しくする
+    fn upper_bound(&self) -> u32 {
+        unimplemented!("A* などの厳密な探索のために実装する")
+    }
+    // 今の状態で action を選ぶことを表す識別子
+    // 同じ値を返す組どうしで方策の重みを共有する
+    fn action_key(&self, _action: Self::Action) -> u64 {
+        unimplemented!("NRPA のために実装する")
+    }
+    // 似ているとみなす状態で同じ値を返す
+    fn diversity_key(&self) -> u64 {
+        unimplemented!("多様性を保つビームサーチのために実装する")
+    }
 }
@@ -19,4 +19,13 @@ impl TimeKeeper {
     fn time_over(&self) -> bool {
         self.instant.elapsed() >= self.threshold
     }
+
+    fn elapsed(&self) -> Duration {
+        self.instant.elapsed()
+    }
+
+    // 制限時間のうち経過した割合。0.0 から 1.0
+    fn elapsed_rate(&self) -> f64 {
+        (self.instant.elapsed().as_secs_f64() / self.threshold.as_secs_f64()).min(1.0)
+    }
 }
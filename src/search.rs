@@ -1,3 +1,40 @@
 pub mod alternate;
 pub mod heuristic;
 pub mod one_player;
+
+use ::std::time::Duration;
+
+// choose_with_stats() が返す探索の統計
+// 探索手法によっては数えないものもあり、その場合は 0 や空のまま
+#[derive(Clone, Debug)]
+pub struct SearchStats<A = ()> {
+    // 生成した (MCTS 系では木に残っている) ノードの数
+    pub nodes: u64,
+    // プレイアウトや盤面評価の回数 (MCTS 系では反復回数)
+    pub playouts: u64,
+    // ルートから数えた最大の深さ
+    pub max_depth: u32,
+    pub elapsed: Duration,
+    // ルートの各合法手の統計
+    pub root_actions: Vec<RootActionStats<A>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct RootActionStats<A> {
+    pub action: A,
+    pub visits: u64,
+    // ルートの手番のプレイヤーから見た評価値 (勝率など)
+    pub value: f64,
+}
+
+impl<A> Default for SearchStats<A> {
+    fn default() -> Self {
+        Self {
+            nodes: 0,
+            playouts: 0,
+            max_depth: 0,
+            elapsed: Duration::ZERO,
+            root_actions: Vec::new(),
+        }
+    }
+}
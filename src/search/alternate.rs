@@ -1,16 +1,42 @@
 pub mod alpha_beta;
+pub mod dot;
+pub mod epsilon_greedy;
+pub mod greedy;
 pub mod iterative_deepening_alpha_beta;
 pub mod mcts;
 pub mod mini_max;
 pub mod primitive_montecarlo;
+pub mod puct;
 pub mod random;
 pub mod thuder;
 
-use crate::game::alternate::AlternateGameState;
+use ::std::time::Instant;
+
+use crate::{game::alternate::AlternateGameState, search::SearchStats};
 
 pub trait ChooseAction<S>
 where
     S: AlternateGameState,
 {
     fn choose(&self, state: &S) -> S::Action;
+    // 統計を取らない探索では経過時間だけ埋める
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let instant = Instant::now();
+        let action = self.choose(state);
+        let stats = SearchStats {
+            elapsed: instant.elapsed(),
+            ..SearchStats::default()
+        };
+        (action, stats)
+    }
+}
+
+pub trait Policy<S>
+where
+    S: AlternateGameState,
+{
+    // 合法手とその事前確率の組を返す
+    fn priors(&self, state: &S) -> Vec<(S::Action, f64)>;
+    // 手番のプレイヤーから見た勝率 (0.0 から 1.0) の推定値を返す
+    fn value(&self, state: &S) -> f64;
 }
@@ -1,6 +1,10 @@
 use ::std::time::Duration;
 
-use crate::{game::alternate::AlternateGameState, search::alternate::ChooseAction, TimeKeeper};
+use crate::{
+    game::alternate::AlternateGameState,
+    search::{alternate::ChooseAction, SearchStats},
+    TimeKeeper,
+};
 
 pub struct AlphaBeta {
     depth: u32,
@@ -18,8 +22,16 @@ where
     S: AlternateGameState,
 {
     fn choose(&self, state: &S) -> S::Action {
-        alpha_beta_inner(state, self.depth, &TimeKeeper::new(self.threshold))
-            .unwrap_or_else(|| panic!("1手にかけられる時間が短い。thresholdを調節する"))
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let time_keeper = TimeKeeper::new(self.threshold);
+        let mut stats = SearchStats::default();
+        let action = alpha_beta_inner(state, self.depth, &time_keeper, &mut stats)
+            .unwrap_or_else(|| panic!("1手にかけられる時間が短い。thresholdを調節する"));
+        stats.elapsed = time_keeper.elapsed();
+        (action, stats)
     }
 }
 
@@ -57,7 +69,9 @@ fn search<T>(
     alpha: SearchResult<T>,
     beta: SearchResult<T>,
     d: u32,
+    ply: u32,
     t: &TimeKeeper,
+    stats: &mut SearchStats<T::Action>,
 ) -> Option<SearchResult<T>>
 where
     T: AlternateGameState,
@@ -65,6 +79,8 @@ where
     if t.time_over() {
         return None;
     }
+    stats.nodes += 1;
+    stats.max_depth = stats.max_depth.max(ply);
     if s.done() || d == 0 {
         return Some(SearchResult::Score(s.score()));
     }
@@ -76,7 +92,7 @@ where
     for action in legal_actions {
         let mut next_s = s.clone();
         next_s.advance(action);
-        let score = search(&next_s, beta.neg(), alpha.neg(), d - 1, t)?
+        let score = search(&next_s, beta.neg(), alpha.neg(), d - 1, ply + 1, t, stats)?
             .neg()
             .score();
         if t.time_over() {
@@ -103,13 +119,14 @@ pub(crate) fn alpha_beta_inner<T>(
     state: &T,
     depth: u32,
     time_keeper: &TimeKeeper,
+    stats: &mut SearchStats<T::Action>,
 ) -> Option<T::Action>
 where
     T: AlternateGameState,
 {
     let alpha = SearchResult::Score(i16::MIN + 1); // .neg()をしてもオーバーフローしないように+1
     let beta = SearchResult::Score(i16::MAX);
-    match search(state, alpha, beta, depth, time_keeper)? {
+    match search(state, alpha, beta, depth, 0, time_keeper, stats)? {
         SearchResult::Score(_) => unimplemented!("stateから遷移できる状態がない"),
         SearchResult::ScoreAndAction(_, action) => Some(action),
     }
@@ -0,0 +1,94 @@
+use ::std::{
+    fmt::Debug,
+    io::{self, Write},
+};
+
+use crate::game::alternate::AlternateGameState;
+
+// MCTS 系の探索木を graphviz の DOT 形式で書き出すための設定
+// $ dot -Tsvg tree.dot -o tree.svg
+pub struct DotConfig {
+    // ルートを深さ 0 として max_depth までのノードを書き出す
+    pub max_depth: u32,
+    // ノードのラベルに state の Debug 出力を含めるか
+    pub with_state: bool,
+}
+
+impl DotConfig {
+    pub fn new(max_depth: u32, with_state: bool) -> Self {
+        Self {
+            max_depth,
+            with_state,
+        }
+    }
+}
+
+// 書き出しに必要な情報を各探索の Node から取り出す
+pub(crate) trait DotNode<S>
+where
+    S: AlternateGameState,
+{
+    fn state(&self) -> &S;
+    fn attempt(&self) -> u32;
+    // state の手番のプレイヤーから見た勝ちの合計
+    fn win(&self) -> f64;
+    fn children(&self) -> Vec<(S::Action, &Self)>;
+}
+
+pub(crate) fn write_dot<S, N, W>(root: &N, config: &DotConfig, w: &mut W) -> io::Result<()>
+where
+    S: AlternateGameState + Debug,
+    S::Action: Debug,
+    N: DotNode<S>,
+    W: Write,
+{
+    writeln!(w, "digraph {{")?;
+    writeln!(w, "  node [shape=box, fontname=\"monospace\"];")?;
+    let mut next_id = 0;
+    write_node(root, 0, &mut next_id, config, w)?;
+    writeln!(w, "}}")
+}
+
+// id を振ってノードと子への辺を書き、振った id を返す
+fn write_node<S, N, W>(
+    node: &N,
+    depth: u32,
+    next_id: &mut usize,
+    config: &DotConfig,
+    w: &mut W,
+) -> io::Result<usize>
+where
+    S: AlternateGameState + Debug,
+    S::Action: Debug,
+    N: DotNode<S>,
+    W: Write,
+{
+    let id = *next_id;
+    *next_id += 1;
+    let mut label = format!("visits: {}\\l", node.attempt());
+    if node.attempt() > 0 {
+        let win_rate = node.win() / f64::from(node.attempt());
+        label.push_str(&format!("win rate: {:.3}\\l", win_rate));
+    }
+    if config.with_state {
+        label.push_str(&escape(&format!("{:?}", node.state())));
+        label.push_str("\\l");
+    }
+    writeln!(w, "  {} [label=\"{}\"];", id, label)?;
+    if depth < config.max_depth {
+        for (action, child) in node.children() {
+            let child_id = write_node(child, depth + 1, next_id, config, w)?;
+            let action = escape(&format!("{:?}", action));
+            writeln!(w, "  {} -> {} [label=\"{}\"];", id, child_id, action)?;
+        }
+    }
+    Ok(id)
+}
+
+// DOT のラベル用に、改行を左寄せの改行 \l にしてダブルクオートをエスケープする
+fn escape(s: &str) -> String {
+    s.trim_end()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\l")
+}
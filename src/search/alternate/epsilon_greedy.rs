@@ -0,0 +1,31 @@
+use ::rand::{thread_rng, Rng};
+
+use crate::{
+    game::alternate::AlternateGameState,
+    search::alternate::{greedy::Greedy, random::Random, ChooseAction},
+};
+
+// 確率 epsilon でランダム、それ以外は Greedy
+pub struct EpsilonGreedy {
+    epsilon: f64,
+}
+
+impl EpsilonGreedy {
+    pub fn new(epsilon: f64) -> Self {
+        Self { epsilon }
+    }
+}
+
+impl<S> ChooseAction<S> for EpsilonGreedy
+where
+    S: AlternateGameState,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        let mut rng = thread_rng();
+        if rng.gen_bool(self.epsilon) {
+            (Random {}).choose(state)
+        } else {
+            (Greedy {}).choose(state)
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{game::alternate::AlternateGameState, search::alternate::ChooseAction};
+
+// 1手先の score() だけを見る
+// プレイアウトの方策としても使う
+pub struct Greedy {}
+
+impl<S> ChooseAction<S> for Greedy
+where
+    S: AlternateGameState,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        state
+            .legal_actions()
+            .into_iter()
+            .max_by_key(|&action| {
+                let mut next_state = state.clone();
+                next_state.advance(action);
+                // next_state.score() は相手視点なので -1 倍する
+                -next_state.score()
+            })
+            .unwrap()
+    }
+}
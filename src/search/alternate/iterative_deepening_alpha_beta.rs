@@ -2,7 +2,10 @@ use ::std::time::Duration;
 
 use crate::{
     game::alternate::AlternateGameState,
-    search::alternate::{alpha_beta::alpha_beta_inner, ChooseAction},
+    search::{
+        alternate::{alpha_beta::alpha_beta_inner, ChooseAction},
+        SearchStats,
+    },
     TimeKeeper,
 };
 
@@ -21,13 +24,21 @@ where
     S: AlternateGameState,
 {
     fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
         let time_keeper = TimeKeeper::new(self.threshold);
+        // 各深さでの探索の統計を合算する
+        let mut stats = SearchStats::default();
         let mut best_action = None;
         for depth in 1.. {
-            if let Some(action) = alpha_beta_inner(state, depth, &time_keeper) {
+            if let Some(action) = alpha_beta_inner(state, depth, &time_keeper, &mut stats) {
                 best_action = Some(action);
             } else {
-                return best_action.unwrap_or_else(|| panic!("深さ1の探索でも時間切れ"));
+                stats.elapsed = time_keeper.elapsed();
+                let action = best_action.unwrap_or_else(|| panic!("深さ1の探索でも時間切れ"));
+                return (action, stats);
             }
         }
         unreachable!()
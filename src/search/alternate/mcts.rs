@@ -1,45 +1,115 @@
-use ::std::time::Duration;
+use ::std::{
+    fmt::Debug,
+    io::{self, Write},
+    time::Duration,
+};
 
 use crate::{
     game::alternate::{AlternateGameState, WinningStatus},
-    search::alternate::{primitive_montecarlo::playout, ChooseAction},
+    search::{
+        alternate::{
+            dot::{write_dot, DotConfig, DotNode},
+            primitive_montecarlo::playout,
+            random::Random,
+            ChooseAction,
+        },
+        RootActionStats, SearchStats,
+    },
     TimeKeeper,
 };
 
 #[allow(clippy::upper_case_acronyms)]
-pub struct MCTS {
+pub struct MCTS<P = Random> {
     threshold: Duration,
+    policy: P,
+    playout_depth: Option<u32>,
 }
 
 impl MCTS {
     pub fn new(threshold: Duration) -> Self {
-        Self { threshold }
+        Self::with_playout(threshold, Random {}, None)
+    }
+}
+
+impl<P> MCTS<P> {
+    // PrimitiveMontecarlo::with_playout と同じ
+    pub fn with_playout(threshold: Duration, policy: P, playout_depth: Option<u32>) -> Self {
+        Self {
+            threshold,
+            policy,
+            playout_depth,
+        }
+    }
+
+    // 探索してできた木を DOT 形式で w に書き出す
+    pub fn write_dot<S, W>(&self, state: &S, config: &DotConfig, w: &mut W) -> io::Result<()>
+    where
+        S: AlternateGameState + Debug,
+        S::Action: Debug,
+        P: ChooseAction<S>,
+        W: Write,
+    {
+        let (root, _) = self.search(state, &TimeKeeper::new(self.threshold));
+        write_dot(&root, config, w)
+    }
+
+    // (探索後のルート, 反復回数)
+    fn search<S>(&self, state: &S, time_keeper: &TimeKeeper) -> (Node<S>, u64)
+    where
+        S: AlternateGameState,
+        P: ChooseAction<S>,
+    {
+        let mut root = Node::new(state.clone());
+        root.expand(&state.legal_actions());
+        let mut iterations = 0;
+        while !time_keeper.time_over() {
+            root.evaluate(&self.policy, self.playout_depth);
+            iterations += 1;
+        }
+        (root, iterations)
     }
 }
 
-impl<S> ChooseAction<S> for MCTS
+impl<S, P> ChooseAction<S> for MCTS<P>
 where
     S: AlternateGameState,
+    P: ChooseAction<S>,
 {
-    fn choose(&self, state: &S) -> <S as AlternateGameState>::Action {
+    fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
         let time_keeper = TimeKeeper::new(self.threshold);
-        let mut root = Node::new(state.clone());
+        let mut stats = SearchStats::default();
         let legal_actions = state.legal_actions();
-        root.expand(&legal_actions);
-        for _ in 0.. {
-            if time_keeper.time_over() {
-                break;
-            }
-            root.evaluate();
-        }
+        let (root, iterations) = self.search(state, &time_keeper);
+        stats.playouts = iterations;
         // legal_actions[i] と root.child_nodes[i] が対応している
         assert_eq!(legal_actions.len(), root.child_nodes.len());
-        let (action, _) = legal_actions
+        (stats.nodes, stats.max_depth) = root.size_and_depth();
+        stats.elapsed = time_keeper.elapsed();
+        stats.root_actions = legal_actions
             .into_iter()
-            .zip(root.child_nodes)
-            .max_by_key(|(_, node)| node.attempt)
-            .unwrap();
-        action
+            .zip(&root.child_nodes)
+            .map(|(action, node)| RootActionStats {
+                action,
+                visits: u64::from(node.attempt),
+                // 子視点の勝率を root 視点にする
+                value: if node.attempt == 0 {
+                    0.0
+                } else {
+                    1.0 - node.win / f64::from(node.attempt)
+                },
+            })
+            .collect();
+        let action = stats
+            .root_actions
+            .iter()
+            .max_by_key(|root_action| root_action.visits)
+            .unwrap()
+            .action;
+        (action, stats)
     }
 }
 
@@ -63,6 +133,14 @@ where
         }
     }
 
+    // (部分木のノード数, 部分木の深さ)
+    fn size_and_depth(&self) -> (u64, u32) {
+        self.child_nodes.iter().fold((1, 0), |(size, depth), node| {
+            let (s, d) = node.size_and_depth();
+            (size + s, depth.max(d + 1))
+        })
+    }
+
     fn expand(&mut self, legal_actions: &Vec<S::Action>) {
         assert!(self.child_nodes.is_empty());
         for &action in legal_actions {
@@ -72,7 +150,10 @@ where
         }
     }
 
-    fn evaluate(&mut self) -> f64 {
+    fn evaluate<P>(&mut self, policy: &P, playout_depth: Option<u32>) -> f64
+    where
+        P: ChooseAction<S>,
+    {
         if let Some(status) = self.state.winning_status() {
             let value = match status {
                 WinningStatus::Win => 1.0,
@@ -85,7 +166,7 @@ where
         } else if self.child_nodes.is_empty() {
             const EXPAND_THRESHOLD: u32 = 10;
             let mut state = self.state.clone();
-            let value = playout(&mut state);
+            let value = playout(&mut state, policy, playout_depth);
             self.win += value;
             self.attempt += 1;
             if self.attempt == EXPAND_THRESHOLD {
@@ -124,10 +205,36 @@ where
                     })
                     .unwrap()
             };
-            let value = 1.0 - self.child_nodes[index].evaluate();
+            let value = 1.0 - self.child_nodes[index].evaluate(policy, playout_depth);
             self.win += value;
             self.attempt += 1;
             value
         }
     }
 }
+
+impl<S> DotNode<S> for Node<S>
+where
+    S: AlternateGameState,
+{
+    fn state(&self) -> &S {
+        &self.state
+    }
+
+    fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    fn win(&self) -> f64 {
+        self.win
+    }
+
+    fn children(&self) -> Vec<(S::Action, &Self)> {
+        // expand() と同じく legal_actions() の順に子ノードが並んでいる
+        self.state
+            .legal_actions()
+            .into_iter()
+            .zip(&self.child_nodes)
+            .collect()
+    }
+}
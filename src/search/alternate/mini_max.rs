@@ -1,4 +1,9 @@
-use crate::{game::alternate::AlternateGameState, search::alternate::ChooseAction};
+use ::std::time::Instant;
+
+use crate::{
+    game::alternate::AlternateGameState,
+    search::{alternate::ChooseAction, SearchStats},
+};
 
 pub struct MiniMax {
     depth: u32,
@@ -15,6 +20,10 @@ where
     S: AlternateGameState,
 {
     fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
         // 気持ちとしては S::Action だが
         // 「can't use generic parameters from outer function」なので S とは別に T を用意する
         enum SearchResult<T>
@@ -24,10 +33,12 @@ where
             Score(i16),                     // leaf node
             ScoreAndAction(i16, T::Action), // internal
         }
-        fn search<T>(s: &T, d: u32) -> SearchResult<T>
+        fn search<T>(s: &T, d: u32, ply: u32, stats: &mut SearchStats<T::Action>) -> SearchResult<T>
         where
             T: AlternateGameState,
         {
+            stats.nodes += 1;
+            stats.max_depth = stats.max_depth.max(ply);
             if s.done() || d == 0 {
                 return SearchResult::Score(s.score());
             }
@@ -37,7 +48,7 @@ where
                     let mut next_s = s.clone();
                     next_s.advance(action);
                     // 先手/後手によらずscore最大化で済むように-1倍する
-                    let score = -match search(&next_s, d - 1) {
+                    let score = -match search(&next_s, d - 1, ply + 1, stats) {
                         SearchResult::Score(score) => score,
                         SearchResult::ScoreAndAction(score, _) => score,
                     };
@@ -51,9 +62,13 @@ where
                 },
             )
         }
-        match search(state, self.depth) {
+        let instant = Instant::now();
+        let mut stats = SearchStats::default();
+        let action = match search(state, self.depth, 0, &mut stats) {
             SearchResult::Score(_) => unimplemented!("stateから遷移できる状態がない"),
             SearchResult::ScoreAndAction(_, action) => action,
-        }
+        };
+        stats.elapsed = instant.elapsed();
+        (action, stats)
     }
 }
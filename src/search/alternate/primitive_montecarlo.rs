@@ -2,29 +2,52 @@ use ::std::time::Duration;
 
 use crate::{
     game::alternate::{AlternateGameState, WinningStatus},
-    search::alternate::{random::Random, ChooseAction},
+    search::{
+        alternate::{random::Random, ChooseAction},
+        RootActionStats, SearchStats,
+    },
     TimeKeeper,
 };
 
-pub struct PrimitiveMontecarlo {
+pub struct PrimitiveMontecarlo<P = Random> {
     threshold: Duration,
+    policy: P,
+    playout_depth: Option<u32>,
 }
 
 impl PrimitiveMontecarlo {
     pub fn new(threshold: Duration) -> Self {
-        Self { threshold }
+        Self::with_playout(threshold, Random {}, None)
     }
 }
 
-impl<S> ChooseAction<S> for PrimitiveMontecarlo
+impl<P> PrimitiveMontecarlo<P> {
+    // policy: プレイアウトで手を選ぶ方策
+    // playout_depth: Some(d) なら d 手で打ち切って score_rate() を返す
+    pub fn with_playout(threshold: Duration, policy: P, playout_depth: Option<u32>) -> Self {
+        Self {
+            threshold,
+            policy,
+            playout_depth,
+        }
+    }
+}
+
+impl<S, P> ChooseAction<S> for PrimitiveMontecarlo<P>
 where
     S: AlternateGameState,
+    P: ChooseAction<S>,
 {
     fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
         let time_keeper = TimeKeeper::new(self.threshold);
+        let mut stats = SearchStats::default();
         let legal_actions = state.legal_actions();
         let mut values = vec![0.0; legal_actions.len()];
-        let mut counts = vec![0; legal_actions.len()];
+        let mut counts = vec![0u32; legal_actions.len()];
         for i in 0.. {
             if time_keeper.time_over() {
                 break;
@@ -32,8 +55,9 @@ where
             let i = i % legal_actions.len();
             let mut next_state = state.clone();
             next_state.advance(legal_actions[i]);
-            values[i] += 1.0 - playout(&mut next_state);
+            values[i] += 1.0 - playout(&mut next_state, &self.policy, self.playout_depth);
             counts[i] += 1;
+            stats.playouts += 1;
         }
         let arg_max = (0..legal_actions.len())
             .max_by(|&i, &j| {
@@ -42,23 +66,47 @@ where
                 left.total_cmp(&right)
             })
             .unwrap();
-        legal_actions[arg_max]
+        stats.elapsed = time_keeper.elapsed();
+        stats.root_actions = (0..legal_actions.len())
+            .map(|i| RootActionStats {
+                action: legal_actions[i],
+                visits: u64::from(counts[i]),
+                value: if counts[i] == 0 {
+                    0.0
+                } else {
+                    values[i] / f64::from(counts[i])
+                },
+            })
+            .collect();
+        (legal_actions[arg_max], stats)
     }
 }
 
-pub fn playout<S>(state: &mut S) -> f64
+// 決着がつくか depth 手進めるまで policy で手を選び、手番のプレイヤーから見た勝率を返す
+pub fn playout<S, P>(state: &mut S, policy: &P, depth: Option<u32>) -> f64
 where
     S: AlternateGameState,
+    P: ChooseAction<S>,
 {
-    match state.winning_status() {
-        Some(status) => match status {
-            WinningStatus::Win => 1.0,
-            WinningStatus::Draw => 0.5,
-            WinningStatus::Lose => 0.0,
-        },
-        None => {
-            state.advance((Random {}).choose(state));
-            1.0 - playout(state)
+    // 再帰せずに進めて、最後に手数の偶奇で視点を戻す
+    let mut plies = 0;
+    let value = loop {
+        if let Some(status) = state.winning_status() {
+            break match status {
+                WinningStatus::Win => 1.0,
+                WinningStatus::Draw => 0.5,
+                WinningStatus::Lose => 0.0,
+            };
+        }
+        if depth == Some(plies) {
+            break state.score_rate();
         }
+        state.advance(policy.choose(state));
+        plies += 1;
+    };
+    if plies % 2 == 0 {
+        value
+    } else {
+        1.0 - value
     }
 }
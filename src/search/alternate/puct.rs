@@ -0,0 +1,252 @@
+use ::std::{
+    fmt::Debug,
+    io::{self, Write},
+    time::Duration,
+};
+
+use crate::{
+    game::alternate::{AlternateGameState, WinningStatus},
+    search::{
+        alternate::{
+            dot::{write_dot, DotConfig, DotNode},
+            ChooseAction, Policy,
+        },
+        RootActionStats, SearchStats,
+    },
+    TimeKeeper,
+};
+
+// AlphaZero 風の探索
+// UCB1 の代わりに事前確率つきの PUCT で子ノードを選び、プレイアウトの代わりに Policy::value で評価する
+#[allow(clippy::upper_case_acronyms)]
+pub struct PUCT<P> {
+    policy: P,
+    c_puct: f64,
+    threshold: Duration,
+}
+
+impl<P> PUCT<P> {
+    pub fn new(policy: P, c_puct: f64, threshold: Duration) -> Self {
+        Self {
+            policy,
+            c_puct,
+            threshold,
+        }
+    }
+
+    // MCTS::write_dot と同じ
+    pub fn write_dot<S, W>(&self, state: &S, config: &DotConfig, w: &mut W) -> io::Result<()>
+    where
+        S: AlternateGameState + Debug,
+        S::Action: Debug,
+        P: Policy<S>,
+        W: Write,
+    {
+        let (root, _) = self.search(state, &TimeKeeper::new(self.threshold));
+        write_dot(&root, config, w)
+    }
+
+    // (探索後のルート, 反復回数)
+    fn search<S>(&self, state: &S, time_keeper: &TimeKeeper) -> (Node<S>, u64)
+    where
+        S: AlternateGameState,
+        P: Policy<S>,
+    {
+        let mut root = Node::new(state.clone(), 1.0);
+        root.expand(&self.policy);
+        let mut iterations = 0;
+        while !time_keeper.time_over() {
+            root.evaluate(&self.policy, self.c_puct);
+            iterations += 1;
+        }
+        (root, iterations)
+    }
+}
+
+impl<S, P> ChooseAction<S> for PUCT<P>
+where
+    S: AlternateGameState,
+    P: Policy<S>,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let time_keeper = TimeKeeper::new(self.threshold);
+        let mut stats = SearchStats::default();
+        let (root, iterations) = self.search(state, &time_keeper);
+        stats.playouts = iterations;
+        (stats.nodes, stats.max_depth) = root.size_and_depth();
+        stats.elapsed = time_keeper.elapsed();
+        stats.root_actions = root
+            .child_nodes
+            .iter()
+            .map(|(action, node)| RootActionStats {
+                action: *action,
+                visits: u64::from(node.attempt),
+                value: if node.attempt == 0 {
+                    0.0
+                } else {
+                    1.0 - node.win / f64::from(node.attempt)
+                },
+            })
+            .collect();
+        let action = stats
+            .root_actions
+            .iter()
+            .max_by_key(|root_action| root_action.visits)
+            .unwrap()
+            .action;
+        (action, stats)
+    }
+}
+
+// 合法手の事前確率は一様、盤面の評価は score_rate() を使う
+pub struct UniformPolicy {}
+
+impl<S> Policy<S> for UniformPolicy
+where
+    S: AlternateGameState,
+{
+    fn priors(&self, state: &S) -> Vec<(S::Action, f64)> {
+        let legal_actions = state.legal_actions();
+        let prior = 1.0 / legal_actions.len() as f64;
+        legal_actions
+            .into_iter()
+            .map(|action| (action, prior))
+            .collect()
+    }
+
+    fn value(&self, state: &S) -> f64 {
+        state.score_rate()
+    }
+}
+
+struct Node<S>
+where
+    S: AlternateGameState,
+{
+    state: S,
+    prior: f64,
+    attempt: u32,
+    win: f64,
+    child_nodes: Vec<(S::Action, Node<S>)>,
+}
+
+impl<S> Node<S>
+where
+    S: AlternateGameState,
+{
+    fn new(state: S, prior: f64) -> Self {
+        Self {
+            state,
+            prior,
+            attempt: 0,
+            win: 0.0,
+            child_nodes: Vec::new(),
+        }
+    }
+
+    // (部分木のノード数, 部分木の深さ)
+    fn size_and_depth(&self) -> (u64, u32) {
+        self.child_nodes
+            .iter()
+            .fold((1, 0), |(size, depth), (_, node)| {
+                let (s, d) = node.size_and_depth();
+                (size + s, depth.max(d + 1))
+            })
+    }
+
+    fn expand<P>(&mut self, policy: &P)
+    where
+        P: Policy<S>,
+    {
+        assert!(self.child_nodes.is_empty());
+        for (action, prior) in policy.priors(&self.state) {
+            let mut next_state = self.state.clone();
+            next_state.advance(action);
+            self.child_nodes
+                .push((action, Node::new(next_state, prior)));
+        }
+    }
+
+    fn evaluate<P>(&mut self, policy: &P, c_puct: f64) -> f64
+    where
+        P: Policy<S>,
+    {
+        if let Some(status) = self.state.winning_status() {
+            let value = match status {
+                WinningStatus::Win => 1.0,
+                WinningStatus::Draw => 0.5,
+                WinningStatus::Lose => 0.0,
+            };
+            self.win += value;
+            self.attempt += 1;
+            value
+        } else if self.child_nodes.is_empty() {
+            // プレイアウトせずに Policy の評価値をそのまま使う
+            let value = policy.value(&self.state);
+            self.win += value;
+            self.attempt += 1;
+            self.expand(policy);
+            value
+        } else {
+            fn puct<T>(child: &Node<T>, sqrt_t: f64, c_puct: f64) -> f64
+            where
+                T: AlternateGameState,
+            {
+                // 一度も探索していないノードの勝率は 0.5 とみなす
+                let q = if child.attempt == 0 {
+                    0.5
+                } else {
+                    // 子視点の勝率を self 視点にする
+                    1.0 - child.win / f64::from(child.attempt)
+                };
+                q + c_puct * child.prior * sqrt_t / f64::from(1 + child.attempt)
+            }
+            let t = self
+                .child_nodes
+                .iter()
+                .map(|(_, node)| node.attempt)
+                .sum::<u32>();
+            // t = 0 のときも事前確率が効くように 1 以上にする
+            let sqrt_t = f64::from(t.max(1)).sqrt();
+            let index = (0..self.child_nodes.len())
+                .max_by(|&i, &j| {
+                    let left = puct(&self.child_nodes[i].1, sqrt_t, c_puct);
+                    let right = puct(&self.child_nodes[j].1, sqrt_t, c_puct);
+                    left.total_cmp(&right)
+                })
+                .unwrap();
+            let value = 1.0 - self.child_nodes[index].1.evaluate(policy, c_puct);
+            self.win += value;
+            self.attempt += 1;
+            value
+        }
+    }
+}
+
+impl<S> DotNode<S> for Node<S>
+where
+    S: AlternateGameState,
+{
+    fn state(&self) -> &S {
+        &self.state
+    }
+
+    fn attempt(&self) -> u32 {
+        self.attempt
+    }
+
+    fn win(&self) -> f64 {
+        self.win
+    }
+
+    fn children(&self) -> Vec<(S::Action, &Self)> {
+        self.child_nodes
+            .iter()
+            .map(|(action, node)| (*action, node))
+            .collect()
+    }
+}
@@ -1,4 +1,4 @@
-use ::rand::{seq::SliceRandom, thread_rng};
+use ::rand::thread_rng;
 
 use crate::{game::alternate::AlternateGameState, search::alternate::ChooseAction};
 
@@ -10,6 +10,6 @@ where
 {
     fn choose(&self, state: &S) -> S::Action {
         let mut rng = thread_rng();
-        state.legal_actions().choose(&mut rng).copied().unwrap()
+        state.random_action(&mut rng)
     }
 }
@@ -1,12 +1,29 @@
+pub mod genetic;
+pub mod great_deluge;
 pub mod hill_climb;
+pub mod late_acceptance;
+pub mod parallel_tempering;
 pub mod random;
 pub mod simulated_annealing;
+pub mod tabu;
 
-use crate::game::heuristic::HeuristicGameState;
+use ::std::time::Instant;
+
+use crate::{game::heuristic::HeuristicGameState, search::SearchStats};
 
 pub trait ChooseState<S>
 where
     S: HeuristicGameState,
 {
     fn choose(&self, initial_state: &S) -> S;
+    // 統計を取らない探索では経過時間だけ埋める
+    fn choose_with_stats(&self, initial_state: &S) -> (S, SearchStats) {
+        let instant = Instant::now();
+        let state = self.choose(initial_state);
+        let stats = SearchStats {
+            elapsed: instant.elapsed(),
+            ..SearchStats::default()
+        };
+        (state, stats)
+    }
 }
@@ -0,0 +1,94 @@
+use ::std::time::Instant;
+
+use ::rand::{thread_rng, Rng};
+
+use crate::{
+    game::heuristic::HeuristicGameState,
+    search::{heuristic::ChooseState, SearchStats},
+};
+
+// 遺伝的アルゴリズム
+// 適応度は start()、交叉は crossover()、突然変異は transition()
+// 親はトーナメント選択で選び、各世代で一番よい個体はそのまま次の世代に残す
+pub struct GeneticAlgorithm {
+    population: usize,
+    generations: usize,
+    mutation_rate: f64,
+}
+
+impl GeneticAlgorithm {
+    pub fn new(population: usize, generations: usize, mutation_rate: f64) -> Self {
+        assert!(population > 0);
+        assert!((0.0..=1.0).contains(&mutation_rate));
+        Self {
+            population,
+            generations,
+            mutation_rate,
+        }
+    }
+}
+
+impl<S> ChooseState<S> for GeneticAlgorithm
+where
+    S: HeuristicGameState,
+{
+    fn choose(&self, initial_state: &S) -> S {
+        self.choose_with_stats(initial_state).0
+    }
+
+    fn choose_with_stats(&self, initial_state: &S) -> (S, SearchStats) {
+        let instant = Instant::now();
+        let mut stats = SearchStats::default();
+        let mut rng = thread_rng();
+        // (適応度, 個体)
+        let mut population = Vec::new();
+        population.push((initial_state.start(), initial_state.clone()));
+        while population.len() < self.population {
+            let mut state = initial_state.clone();
+            state.initialize();
+            population.push((state.start(), state));
+        }
+        stats.playouts += self.population as u64;
+        for _ in 0..self.generations {
+            let elite = population
+                .iter()
+                .max_by(|(a, _), (b, _)| S::DIRECTION.compare(*a, *b))
+                .cloned()
+                .unwrap();
+            let mut next_population = vec![elite];
+            while next_population.len() < self.population {
+                let (_, parent1) = tournament(&population, &mut rng);
+                let (_, parent2) = tournament(&population, &mut rng);
+                let mut child = parent1.crossover(parent2);
+                if rng.gen_bool(self.mutation_rate) {
+                    child.transition();
+                }
+                stats.nodes += 1;
+                next_population.push((child.start(), child));
+                stats.playouts += 1;
+            }
+            population = next_population;
+        }
+        let (_, best_state) = population
+            .into_iter()
+            .max_by(|(a, _), (b, _)| S::DIRECTION.compare(*a, *b))
+            .unwrap();
+        stats.elapsed = instant.elapsed();
+        (best_state, stats)
+    }
+}
+
+// 2個体を無作為に選んで、よいほうを返す
+fn tournament<'a, S, R>(population: &'a [(S::Objective, S)], rng: &mut R) -> &'a (S::Objective, S)
+where
+    S: HeuristicGameState,
+    R: Rng,
+{
+    let a = &population[rng.gen_range(0..population.len())];
+    let b = &population[rng.gen_range(0..population.len())];
+    if S::DIRECTION.better(b.0, a.0) {
+        b
+    } else {
+        a
+    }
+}
@@ -0,0 +1,65 @@
+use ::std::time::Instant;
+
+use crate::{
+    game::heuristic::{HeuristicGameState, Objective},
+    search::{heuristic::ChooseState, SearchStats},
+};
+
+// Great Deluge
+// 水位を初期解のスコアから毎回 rain_speed ずつ上げ、水位以上か今のスコア以上なら近傍へ移る
+// 最小化なら水位を下げ、水位以下か今のスコア以下なら移る
+pub struct GreatDeluge {
+    transitions: usize,
+    rain_speed: f64,
+}
+
+impl GreatDeluge {
+    pub fn new(transitions: usize, rain_speed: f64) -> Self {
+        assert!(rain_speed >= 0.0);
+        Self {
+            transitions,
+            rain_speed,
+        }
+    }
+}
+
+impl<S> ChooseState<S> for GreatDeluge
+where
+    S: HeuristicGameState,
+{
+    fn choose(&self, initial_state: &S) -> S {
+        self.choose_with_stats(initial_state).0
+    }
+
+    fn choose_with_stats(&self, initial_state: &S) -> (S, SearchStats) {
+        let instant = Instant::now();
+        let mut stats = SearchStats::default();
+        let mut state = initial_state.clone();
+        let mut score = state.start();
+        stats.playouts += 1;
+        let mut best_state = state.clone();
+        let mut best_score = score;
+        // よくなると大きくなるように符号をそろえた水位
+        let mut level = S::DIRECTION.gain(score.to_f64());
+        for _ in 0..self.transitions {
+            let mut next_state = state.clone();
+            next_state.transition();
+            stats.nodes += 1;
+            let next_score = next_state.start();
+            stats.playouts += 1;
+            if level <= S::DIRECTION.gain(next_score.to_f64())
+                || !S::DIRECTION.better(score, next_score)
+            {
+                score = next_score;
+                state = next_state;
+                if S::DIRECTION.better(score, best_score) {
+                    best_score = score;
+                    best_state = state.clone();
+                }
+            }
+            level += self.rain_speed;
+        }
+        stats.elapsed = instant.elapsed();
+        (best_state, stats)
+    }
+}
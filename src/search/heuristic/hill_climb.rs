@@ -1,31 +1,114 @@
-use crate::{game::heuristic::HeuristicGameState, search::heuristic::ChooseState};
+use ::std::time::Instant;
+
+use crate::{
+    game::heuristic::{HeuristicGameState, IncrementalHeuristicGameState},
+    search::{heuristic::ChooseState, SearchStats},
+};
 
 pub struct HillClimb {
     transitions: usize,
+    restarts: usize,
+    restart: Restart,
+}
+
+// 局所最適に着いたあとの始めなおし方
+pub enum Restart {
+    // initialize() で作りなおした初期解から登る (多点スタート)
+    Initialize,
+    // それまでの最良を perturb() で大きく動かしてから登る (反復局所探索)
+    Perturb,
 }
 
 impl HillClimb {
     pub fn new(transitions: usize) -> Self {
-        Self { transitions }
+        Self {
+            transitions,
+            restarts: 0,
+            restart: Restart::Initialize,
+        }
     }
-}
 
-impl<S> ChooseState<S> for HillClimb
-where
-    S: HeuristicGameState,
-{
-    fn choose(&self, initial_state: &S) -> S {
-        let mut state = initial_state.clone();
+    // 1回 transitions 回の山登りを、始めなおして restarts 回多くやる
+    pub fn with_restarts(mut self, restarts: usize, restart: Restart) -> Self {
+        self.restarts = restarts;
+        self.restart = restart;
+        self
+    }
+
+    // state から transitions 回山登りして、(登りきった状態, そのスコア) を返す
+    fn climb<S>(&self, mut state: S, stats: &mut SearchStats) -> (S, S::Objective)
+    where
+        S: HeuristicGameState,
+    {
         let mut best_score = state.start();
+        stats.playouts += 1;
         for _ in 0..self.transitions {
             let mut next_state = state.clone();
             next_state.transition();
+            stats.nodes += 1;
             let next_score = next_state.start();
-            if best_score < next_score {
+            stats.playouts += 1;
+            if S::DIRECTION.better(next_score, best_score) {
                 best_score = next_score;
                 state = next_state;
             }
         }
-        state
+        (state, best_score)
+    }
+
+    // choose_with_stats() と同じだが、clone() と start() のかわりに delta() で近傍を評価する
+    // 始めなおしはしない
+    pub fn choose_incremental<S>(&self, initial_state: &S) -> (S, SearchStats)
+    where
+        S: IncrementalHeuristicGameState,
+    {
+        let instant = Instant::now();
+        let mut stats = SearchStats::default();
+        let mut state = initial_state.clone();
+        for _ in 0..self.transitions {
+            let mv = state.propose();
+            stats.nodes += 1;
+            if S::DIRECTION.gain(state.delta(&mv)) > 0.0 {
+                state.apply(mv);
+            }
+        }
+        stats.elapsed = instant.elapsed();
+        (state, stats)
+    }
+}
+
+impl<S> ChooseState<S> for HillClimb
+where
+    S: HeuristicGameState,
+{
+    fn choose(&self, initial_state: &S) -> S {
+        self.choose_with_stats(initial_state).0
+    }
+
+    fn choose_with_stats(&self, initial_state: &S) -> (S, SearchStats) {
+        let instant = Instant::now();
+        let mut stats = SearchStats::default();
+        let (mut best_state, mut best_score) = self.climb(initial_state.clone(), &mut stats);
+        for _ in 0..self.restarts {
+            let state = match self.restart {
+                Restart::Initialize => {
+                    let mut state = initial_state.clone();
+                    state.initialize();
+                    state
+                }
+                Restart::Perturb => {
+                    let mut state = best_state.clone();
+                    state.perturb();
+                    state
+                }
+            };
+            let (state, score) = self.climb(state, &mut stats);
+            if S::DIRECTION.better(score, best_score) {
+                best_score = score;
+                best_state = state;
+            }
+        }
+        stats.elapsed = instant.elapsed();
+        (best_state, stats)
     }
 }
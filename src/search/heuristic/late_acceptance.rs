@@ -0,0 +1,65 @@
+use ::std::time::Instant;
+
+use crate::{
+    game::heuristic::HeuristicGameState,
+    search::{heuristic::ChooseState, SearchStats},
+};
+
+// Late Acceptance Hill Climbing
+// 今のスコアか history_len 回前のスコアより悪くなければ近傍へ移る
+pub struct LateAcceptance {
+    transitions: usize,
+    history_len: usize,
+}
+
+impl LateAcceptance {
+    pub fn new(transitions: usize, history_len: usize) -> Self {
+        assert!(history_len > 0);
+        Self {
+            transitions,
+            history_len,
+        }
+    }
+}
+
+impl<S> ChooseState<S> for LateAcceptance
+where
+    S: HeuristicGameState,
+{
+    fn choose(&self, initial_state: &S) -> S {
+        self.choose_with_stats(initial_state).0
+    }
+
+    fn choose_with_stats(&self, initial_state: &S) -> (S, SearchStats) {
+        let instant = Instant::now();
+        let mut stats = SearchStats::default();
+        let mut state = initial_state.clone();
+        let mut score = state.start();
+        stats.playouts += 1;
+        let mut best_state = state.clone();
+        let mut best_score = score;
+        // 過去のスコアを環状に持つ
+        let mut history = vec![score; self.history_len];
+        for i in 0..self.transitions {
+            let mut next_state = state.clone();
+            next_state.transition();
+            stats.nodes += 1;
+            let next_score = next_state.start();
+            stats.playouts += 1;
+            let v = i % self.history_len;
+            if !S::DIRECTION.better(history[v], next_score)
+                || !S::DIRECTION.better(score, next_score)
+            {
+                score = next_score;
+                state = next_state;
+                if S::DIRECTION.better(score, best_score) {
+                    best_score = score;
+                    best_state = state.clone();
+                }
+            }
+            history[v] = score;
+        }
+        stats.elapsed = instant.elapsed();
+        (best_state, stats)
+    }
+}
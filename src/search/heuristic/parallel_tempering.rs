@@ -0,0 +1,140 @@
+use ::std::{thread, time::Instant};
+
+use ::rand::{thread_rng, Rng};
+
+use crate::{
+    game::heuristic::{HeuristicGameState, Objective},
+    search::{heuristic::ChooseState, SearchStats},
+};
+
+// 温度を固定した replicas 個の焼きなましをそれぞれ別のスレッドで sweep 回ずつ進め、
+// 隣りあう温度の状態をメトロポリス基準で交換する。これを rounds 回くり返す
+pub struct ParallelTempering {
+    // 低い順
+    temperatures: Vec<f64>,
+    rounds: usize,
+    sweep: usize,
+}
+
+impl ParallelTempering {
+    // 温度は min_temperature から max_temperature まで等比に並べる
+    pub fn new(
+        replicas: usize,
+        min_temperature: f64,
+        max_temperature: f64,
+        rounds: usize,
+        sweep: usize,
+    ) -> Self {
+        assert!(replicas > 0);
+        assert!(0.0 < min_temperature && min_temperature <= max_temperature);
+        let temperatures = (0..replicas)
+            .map(|i| {
+                if replicas == 1 {
+                    min_temperature
+                } else {
+                    let rate = i as f64 / (replicas - 1) as f64;
+                    min_temperature * (max_temperature / min_temperature).powf(rate)
+                }
+            })
+            .collect();
+        Self {
+            temperatures,
+            rounds,
+            sweep,
+        }
+    }
+}
+
+impl<S> ChooseState<S> for ParallelTempering
+where
+    S: HeuristicGameState + Send,
+    S::Objective: Send,
+{
+    fn choose(&self, initial_state: &S) -> S {
+        self.choose_with_stats(initial_state).0
+    }
+
+    fn choose_with_stats(&self, initial_state: &S) -> (S, SearchStats) {
+        let instant = Instant::now();
+        let mut stats = SearchStats::default();
+        let mut rng = thread_rng();
+        let score = initial_state.start();
+        stats.playouts += 1;
+        let mut replicas = self
+            .temperatures
+            .iter()
+            .map(|_| Replica {
+                state: initial_state.clone(),
+                score,
+                best_state: initial_state.clone(),
+                best_score: score,
+            })
+            .collect::<Vec<_>>();
+        for _ in 0..self.rounds {
+            let transitions = thread::scope(|scope| {
+                let handles = replicas
+                    .iter_mut()
+                    .zip(&self.temperatures)
+                    .map(|(replica, &t)| scope.spawn(move || replica.anneal(t, self.sweep)))
+                    .collect::<Vec<_>>();
+                handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap())
+                    .sum::<u64>()
+            });
+            stats.nodes += transitions;
+            stats.playouts += transitions;
+            for i in 1..replicas.len() {
+                // 低温側のほうが悪ければ必ず交換する
+                let beta = 1.0 / self.temperatures[i - 1] - 1.0 / self.temperatures[i];
+                let gain =
+                    S::DIRECTION.gain(replicas[i].score.to_f64() - replicas[i - 1].score.to_f64());
+                if rng.gen_bool(f64::exp(beta * gain).min(1.0)) {
+                    // 各レプリカの最良も一緒に動くが、最後は全体の最良を取るので構わない
+                    replicas.swap(i - 1, i);
+                }
+            }
+        }
+        let best = replicas
+            .into_iter()
+            .max_by(|a, b| S::DIRECTION.compare(a.best_score, b.best_score))
+            .unwrap();
+        stats.elapsed = instant.elapsed();
+        (best.best_state, stats)
+    }
+}
+
+struct Replica<S>
+where
+    S: HeuristicGameState,
+{
+    state: S,
+    score: S::Objective,
+    best_state: S,
+    best_score: S::Objective,
+}
+
+impl<S> Replica<S>
+where
+    S: HeuristicGameState,
+{
+    // 温度 t で transitions 回遷移を試し、試した回数を返す
+    fn anneal(&mut self, t: f64, transitions: usize) -> u64 {
+        let mut rng = thread_rng();
+        for _ in 0..transitions {
+            let mut next_state = self.state.clone();
+            next_state.transition();
+            let next_score = next_state.start();
+            let gain = S::DIRECTION.gain(next_score.to_f64() - self.score.to_f64());
+            if gain > 0.0 || rng.gen_bool(f64::exp(gain / t)) {
+                self.score = next_score;
+                self.state = next_state;
+                if S::DIRECTION.better(self.score, self.best_score) {
+                    self.best_score = self.score;
+                    self.best_state = self.state.clone();
+                }
+            }
+        }
+        transitions as u64
+    }
+}
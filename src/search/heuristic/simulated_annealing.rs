@@ -1,21 +1,147 @@
+use ::std::time::{Duration, Instant};
+
 use ::rand::thread_rng;
 use rand::Rng;
 
-use crate::{game::heuristic::HeuristicGameState, search::heuristic::ChooseState};
+use crate::{
+    game::heuristic::{HeuristicGameState, IncrementalHeuristicGameState, Objective},
+    search::{heuristic::ChooseState, SearchStats},
+    TimeKeeper,
+};
 
 pub struct SimulatedAnnealing {
-    transitions: usize,
+    budget: Budget,
     start_temperature: f64,
     end_temperature: f64,
+    schedule: Schedule,
+    // 温度を start_temperature に戻して冷やしなおす回数。1 なら戻さない
+    cycles: u32,
+}
+
+enum Budget {
+    Transitions(usize),
+    Time(Duration),
+}
+
+// 進み具合 (0.0 から 1.0) から温度を決める
+pub enum Schedule {
+    // start_temperature から end_temperature まで一定の割合で下げる
+    Linear,
+    // start_temperature から end_temperature まで一定の比で下げる
+    Exponential,
+    // (start_temperature, end_temperature, 進み具合) -> 温度
+    Custom(Box<dyn Fn(f64, f64, f64) -> f64 + Send + Sync>),
+}
+
+impl Schedule {
+    fn temperature(&self, start: f64, end: f64, progress: f64) -> f64 {
+        match self {
+            Schedule::Linear => start + (end - start) * progress,
+            Schedule::Exponential => start * (end / start).powf(progress),
+            Schedule::Custom(f) => f(start, end, progress),
+        }
+    }
 }
 
 impl SimulatedAnnealing {
     pub fn new(transitions: usize, start_temperature: f64, end_temperature: f64) -> Self {
         Self {
-            transitions,
+            budget: Budget::Transitions(transitions),
+            start_temperature,
+            end_temperature,
+            schedule: Schedule::Linear,
+            cycles: 1,
+        }
+    }
+
+    // 遷移の回数ではなく時間で打ち切る。温度は経過時間から決める
+    pub fn with_time_limit(
+        threshold: Duration,
+        start_temperature: f64,
+        end_temperature: f64,
+    ) -> Self {
+        Self {
+            budget: Budget::Time(threshold),
             start_temperature,
             end_temperature,
+            schedule: Schedule::Linear,
+            cycles: 1,
+        }
+    }
+
+    pub fn with_schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    // 全体を cycles 回に分けて、そのたびに温度を上げなおす
+    pub fn with_reheat(mut self, cycles: u32) -> Self {
+        assert!(cycles > 0);
+        self.cycles = cycles;
+        self
+    }
+
+    // i 回目の遷移での進み具合。打ち切るなら None
+    fn progress(&self, i: usize, time_keeper: Option<&TimeKeeper>) -> Option<f64> {
+        match (&self.budget, time_keeper) {
+            (&Budget::Transitions(transitions), _) => {
+                (i < transitions).then_some(i as f64 / transitions as f64)
+            }
+            (Budget::Time(_), Some(time_keeper)) => {
+                (!time_keeper.time_over()).then(|| time_keeper.elapsed_rate())
+            }
+            (Budget::Time(_), None) => unreachable!(),
+        }
+    }
+
+    fn time_keeper(&self) -> Option<TimeKeeper> {
+        match self.budget {
+            Budget::Transitions(_) => None,
+            Budget::Time(threshold) => Some(TimeKeeper::new(threshold)),
+        }
+    }
+
+    // choose_with_stats() と同じだが、clone() と start() のかわりに delta() で近傍を評価する
+    // 状態を clone() するのは最良が更新されたときだけ
+    pub fn choose_incremental<S>(&self, initial_state: &S) -> (S, SearchStats)
+    where
+        S: IncrementalHeuristicGameState,
+    {
+        let instant = Instant::now();
+        let time_keeper = self.time_keeper();
+        let mut stats = SearchStats::default();
+        let mut rng = thread_rng();
+        let mut state = initial_state.clone();
+        // 初期解からの改善量
+        let mut score = 0.0;
+        let mut best_state = state.clone();
+        let mut best_score = score;
+        for i in 0.. {
+            let Some(progress) = self.progress(i, time_keeper.as_ref()) else {
+                break;
+            };
+            let mv = state.propose();
+            stats.nodes += 1;
+            let gain = S::DIRECTION.gain(state.delta(&mv));
+            let t = self.temperature(progress);
+            if gain > 0.0 || rng.gen_bool(f64::exp(gain / t)) {
+                state.apply(mv);
+                score += gain;
+                if best_score < score {
+                    best_score = score;
+                    best_state = state.clone();
+                }
+            }
         }
+        stats.elapsed = instant.elapsed();
+        (best_state, stats)
+    }
+
+    fn temperature(&self, progress: f64) -> f64 {
+        // 今の周回の中での進み具合
+        let progress = (progress * f64::from(self.cycles)).fract();
+        self.schedule
+            .temperature(self.start_temperature, self.end_temperature, progress)
     }
 }
 
@@ -24,29 +150,40 @@ where
     S: HeuristicGameState,
 {
     fn choose(&self, initial_state: &S) -> S {
+        self.choose_with_stats(initial_state).0
+    }
+
+    fn choose_with_stats(&self, initial_state: &S) -> (S, SearchStats) {
+        let instant = Instant::now();
+        let time_keeper = self.time_keeper();
+        let mut stats = SearchStats::default();
         let mut rng = thread_rng();
         let mut state = initial_state.clone();
         let mut score = state.start();
+        stats.playouts += 1;
         let mut best_state = state.clone();
         let mut best_score = score;
-        for i in 0..self.transitions {
+        for i in 0.. {
+            let Some(progress) = self.progress(i, time_keeper.as_ref()) else {
+                break;
+            };
             let mut next_state = state.clone();
             next_state.transition();
+            stats.nodes += 1;
             let next_score = next_state.start();
-            let t = self.start_temperature
-                + (self.end_temperature - self.start_temperature)
-                    * (i as f64 / self.transitions as f64);
-            if score < next_score
-                || rng.gen_bool(f64::exp((f64::from(next_score) - f64::from(score)) / t))
-            {
+            stats.playouts += 1;
+            let t = self.temperature(progress);
+            let gain = S::DIRECTION.gain(next_score.to_f64() - score.to_f64());
+            if gain > 0.0 || rng.gen_bool(f64::exp(gain / t)) {
                 score = next_score;
                 state = next_state;
-                if best_score < score {
+                if S::DIRECTION.better(score, best_score) {
                     best_score = score;
                     best_state = state.clone();
                 }
             }
         }
-        best_state
+        stats.elapsed = instant.elapsed();
+        (best_state, stats)
     }
 }
@@ -0,0 +1,79 @@
+use ::std::{collections::VecDeque, time::Instant};
+
+use crate::{
+    game::heuristic::IncrementalHeuristicGameState,
+    search::{heuristic::ChooseState, SearchStats},
+};
+
+// 毎回 neighbors 個の近傍を評価して、悪くなっても一番よい近傍へ移る
+// 直近 tenure 回の移動を戻す移動はタブーとして選ばない
+// ただし、それまでの最良を更新するならタブーでも選ぶ
+pub struct TabuSearch {
+    iterations: usize,
+    neighbors: usize,
+    tenure: usize,
+}
+
+impl TabuSearch {
+    pub fn new(iterations: usize, neighbors: usize, tenure: usize) -> Self {
+        assert!(neighbors > 0);
+        Self {
+            iterations,
+            neighbors,
+            tenure,
+        }
+    }
+}
+
+impl<S> ChooseState<S> for TabuSearch
+where
+    S: IncrementalHeuristicGameState,
+    S::Move: PartialEq,
+{
+    fn choose(&self, initial_state: &S) -> S {
+        self.choose_with_stats(initial_state).0
+    }
+
+    fn choose_with_stats(&self, initial_state: &S) -> (S, SearchStats) {
+        let instant = Instant::now();
+        let mut stats = SearchStats::default();
+        let mut state = initial_state.clone();
+        // 初期解からの改善量
+        let mut score = 0.0;
+        let mut best_state = state.clone();
+        let mut best_score = score;
+        let mut tabu = VecDeque::new();
+        for _ in 0..self.iterations {
+            // (改善量, 移動)
+            let mut next = None;
+            for _ in 0..self.neighbors {
+                let mv = state.propose();
+                let gain = S::DIRECTION.gain(state.delta(&mv));
+                stats.nodes += 1;
+                let aspiration = best_score < score + gain;
+                if tabu.contains(&mv) && !aspiration {
+                    continue;
+                }
+                if next.as_ref().map_or(true, |&(g, _)| g < gain) {
+                    next = Some((gain, mv));
+                }
+            }
+            // 全部タブーだった
+            let Some((gain, mv)) = next else {
+                continue;
+            };
+            tabu.push_back(state.reverse_move(&mv));
+            if tabu.len() > self.tenure {
+                tabu.pop_front();
+            }
+            state.apply(mv);
+            score += gain;
+            if best_score < score {
+                best_score = score;
+                best_state = state.clone();
+            }
+        }
+        stats.elapsed = instant.elapsed();
+        (best_state, stats)
+    }
+}
@@ -1,13 +1,103 @@
+pub mod astar;
 pub mod beam;
 pub mod chokudai;
+pub mod exhaustive;
 pub mod greedy;
+pub mod nmcs;
+pub mod nrpa;
+pub mod parallel_beam;
 pub mod random;
+pub mod sp_mcts;
+pub mod tree_beam;
 
-use crate::game::one_player::OnePlayerGameState;
+use ::std::{cmp::Ordering, time::Instant};
+
+use crate::{game::one_player::OnePlayerGameState, search::SearchStats};
 
 pub trait ChooseAction<S>
 where
     S: OnePlayerGameState,
 {
     fn choose(&self, state: &S) -> S::Action;
+    // 統計を取らない探索では経過時間だけ埋める
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let instant = Instant::now();
+        let action = self.choose(state);
+        let stats = SearchStats {
+            elapsed: instant.elapsed(),
+            ..SearchStats::default()
+        };
+        (action, stats)
+    }
+}
+
+// 最初の1手だけでなく、探索で見つけた最良の手順をまとめて返す
+// 一度探索して手順を再生すれば、毎ターン探索しなおさなくて済む
+pub trait PlanActions<S>
+where
+    S: OnePlayerGameState,
+{
+    fn plan(&self, state: &S) -> Vec<S::Action>;
+}
+
+// tree[i] = (親の添字, 親から遷移する手)
+// 親が None ならルートから遷移している
+pub(crate) fn restore_actions<A>(tree: &[(Option<usize>, A)], leaf: Option<usize>) -> Vec<A>
+where
+    A: Copy,
+{
+    let mut actions = Vec::new();
+    let mut index = leaf;
+    while let Some(i) = index {
+        let (parent, action) = tree[i];
+        actions.push(action);
+        index = parent;
+    }
+    actions.reverse();
+    actions
+}
+
+// ビームサーチのヒープに入れる状態
+// heuristic_score() が大きい順、同点なら先に生成した順に取り出す
+#[derive(Clone)]
+pub(crate) struct Candidate<S> {
+    pub(crate) score: i64,
+    // restore_actions() に渡す添字
+    pub(crate) index: Option<usize>,
+    pub(crate) state: S,
 }
+
+impl<S> Candidate<S>
+where
+    S: OnePlayerGameState,
+{
+    pub(crate) fn new(state: S, index: Option<usize>) -> Self {
+        Self {
+            score: state.heuristic_score(),
+            index,
+            state,
+        }
+    }
+}
+
+impl<S> Ord for Candidate<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .cmp(&other.score)
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl<S> PartialOrd for Candidate<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> PartialEq for Candidate<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S> Eq for Candidate<S> {}
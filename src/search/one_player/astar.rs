@@ -0,0 +1,146 @@
+use ::std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashSet},
+    time::Duration,
+};
+
+use crate::{
+    game::one_player::OnePlayerGameState,
+    search::{
+        one_player::{restore_actions, ChooseAction, PlanActions},
+        SearchStats,
+    },
+    TimeKeeper,
+};
+
+// upper_bound() が大きい状態から展開する最良優先探索
+// upper_bound() が過小評価しなければ、最初に取り出した終了状態が最適になる
+pub struct AStar {
+    threshold: Duration,
+    dedup: bool,
+}
+
+impl AStar {
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            dedup: false,
+        }
+    }
+
+    // 一度取り出した状態と state_hash() が等しい状態は展開しない
+    // state_hash() が等しければ evaluate_score() も等しいゲームでだけ使う
+    pub fn with_dedup(mut self) -> Self {
+        self.dedup = true;
+        self
+    }
+
+    // 最適な手順を返す
+    // 時間切れなら、それまでに取り出した一番深い状態への手順を返す
+    fn search<S>(&self, state: &S, stats: &mut SearchStats<S::Action>) -> Vec<S::Action>
+    where
+        S: OnePlayerGameState,
+    {
+        let time_keeper = TimeKeeper::new(self.threshold);
+        let mut tree = Vec::new();
+        let mut heap = BinaryHeap::new();
+        heap.push(Node::new(state.clone(), 0, None));
+        let mut seen = HashSet::new();
+        // (深さ, tree での添字)
+        let mut deepest = (0, None);
+        while let Some(node) = heap.pop() {
+            if time_keeper.time_over() {
+                break;
+            }
+            if node.state.done() {
+                stats.elapsed = time_keeper.elapsed();
+                return restore_actions(&tree, node.index);
+            }
+            if self.dedup && !seen.insert(node.state.state_hash()) {
+                continue;
+            }
+            if deepest.0 < node.depth {
+                deepest = (node.depth, node.index);
+            }
+            for action in node.state.legal_actions() {
+                let mut next_state = node.state.clone();
+                next_state.advance(action);
+                stats.nodes += 1;
+                stats.max_depth = stats.max_depth.max(node.depth + 1);
+                tree.push((node.index, action));
+                heap.push(Node::new(next_state, node.depth + 1, Some(tree.len() - 1)));
+            }
+        }
+        stats.elapsed = time_keeper.elapsed();
+        restore_actions(&tree, deepest.1)
+    }
+}
+
+impl<S> ChooseAction<S> for AStar
+where
+    S: OnePlayerGameState,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let mut stats = SearchStats::default();
+        let actions = self.search(state, &mut stats);
+        (actions[0], stats)
+    }
+}
+
+impl<S> PlanActions<S> for AStar
+where
+    S: OnePlayerGameState,
+{
+    fn plan(&self, state: &S) -> Vec<S::Action> {
+        self.search(state, &mut SearchStats::default())
+    }
+}
+
+struct Node<S> {
+    bound: u32,
+    depth: u32,
+    index: Option<usize>,
+    state: S,
+}
+
+impl<S> Node<S>
+where
+    S: OnePlayerGameState,
+{
+    fn new(state: S, depth: u32, index: Option<usize>) -> Self {
+        Self {
+            bound: state.upper_bound(),
+            depth,
+            index,
+            state,
+        }
+    }
+}
+
+// upper_bound() が大きいほうが大きく、同点なら深いほう、さらに同点なら先に生成したほうが大きい
+impl<S> Ord for Node<S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bound
+            .cmp(&other.bound)
+            .then_with(|| self.depth.cmp(&other.depth))
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+impl<S> PartialOrd for Node<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S> PartialEq for Node<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S> Eq for Node<S> {}
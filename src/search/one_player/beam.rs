@@ -1,55 +1,165 @@
-use ::std::{collections::BinaryHeap, time::Duration};
+use ::std::{
+    collections::{hash_map::Entry, HashMap},
+    mem,
+    time::Duration,
+};
 
-use crate::{game::one_player::OnePlayerGameState, search::one_player::ChooseAction, TimeKeeper};
+use crate::{
+    game::one_player::OnePlayerGameState,
+    search::{
+        one_player::{restore_actions, Candidate, ChooseAction, PlanActions},
+        SearchStats,
+    },
+    TimeKeeper,
+};
 
 pub struct Beam {
     beam_width: usize,
     threshold: Duration,
+    dedup: bool,
+    max_per_bucket: Option<usize>,
 }
 
 impl Beam {
     pub fn new(beam_width: usize, threshold: Duration) -> Self {
+        assert!(beam_width > 0);
         Self {
             beam_width,
             threshold,
+            dedup: false,
+            max_per_bucket: None,
         }
     }
-}
 
-impl<S> ChooseAction<S> for Beam
-where
-    S: OnePlayerGameState + Ord,
-    S::Action: Ord,
-{
-    fn choose(&self, state: &S) -> S::Action {
+    // 同じ深さで state_hash() が等しい状態は一番よいものだけ展開する
+    pub fn with_dedup(mut self) -> Self {
+        self.dedup = true;
+        self
+    }
+
+    // 似た状態ばかりにならないように、同じ深さで diversity_key() が等しい状態は
+    // よいほうから max_per_bucket 個までしか残さない
+    pub fn with_diversity(mut self, max_per_bucket: usize) -> Self {
+        assert!(max_per_bucket > 0);
+        self.max_per_bucket = Some(max_per_bucket);
+        self
+    }
+
+    // 最後まで探索できた深さで一番よい状態への手順を返す
+    fn search<S>(&self, state: &S, stats: &mut SearchStats<S::Action>) -> Vec<S::Action>
+    where
+        S: OnePlayerGameState,
+    {
         let time_keeper = TimeKeeper::new(self.threshold);
-        let mut best_action = None;
-        let mut heap = BinaryHeap::new();
-        heap.push((state.clone(), best_action));
-        loop {
-            let mut new_heap = BinaryHeap::new();
-            for _ in 0..self.beam_width {
+        // 状態そのものではなく手だけを木で持って、最後に手順を復元する
+        let mut tree = Vec::new();
+        let mut best = None;
+        // 深さごとに確保しなおさないように使い回す
+        let mut beam = vec![Candidate::new(state.clone(), None)];
+        let mut children = Vec::new();
+        // state_hash() -> children での位置
+        let mut positions = HashMap::new();
+        // diversity_key() -> 残した数
+        let mut buckets = HashMap::new();
+        // 捨てた状態を取っておいて clone_from() でメモリを使い回す
+        let mut pool: Vec<S> = Vec::new();
+        'search: loop {
+            for now in beam.drain(..) {
                 if time_keeper.time_over() {
-                    return best_action.unwrap();
+                    break 'search;
                 }
-                let Some((now_state, first_action)) = heap.pop() else {
-                    break;
-                };
-                let legal_actions = now_state.legal_actions();
-                for action in legal_actions {
-                    let mut next_state = now_state.clone();
+                for action in now.state.legal_actions() {
+                    let mut next_state = match pool.pop() {
+                        Some(mut next_state) => {
+                            next_state.clone_from(&now.state);
+                            next_state
+                        }
+                        None => now.state.clone(),
+                    };
                     next_state.advance(action);
-                    new_heap.push((next_state, first_action.or(Some(action))));
+                    stats.nodes += 1;
+                    tree.push((now.index, action));
+                    let child = Candidate::new(next_state, Some(tree.len() - 1));
+                    if !self.dedup {
+                        children.push(child);
+                        continue;
+                    }
+                    match positions.entry(child.state.state_hash()) {
+                        Entry::Occupied(entry) => {
+                            let i = *entry.get();
+                            if children[i] < child {
+                                let worse = mem::replace(&mut children[i], child);
+                                pool.push(worse.state);
+                            } else {
+                                pool.push(child.state);
+                            }
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(children.len());
+                            children.push(child);
+                        }
+                    }
                 }
+                pool.push(now.state);
             }
-            heap = new_heap;
-            if let Some((state, action)) = heap.peek() {
-                best_action = *action;
-                if state.done() {
-                    break;
+            // この時点で beam は空になっている
+            if let Some(max_per_bucket) = self.max_per_bucket {
+                // 上位だけでは足りないことがあるので全体をソートして、よい順に詰める
+                children.sort_unstable_by(|a, b| b.cmp(a));
+                for child in children.drain(..) {
+                    let count = buckets.entry(child.state.diversity_key()).or_insert(0);
+                    if beam.len() < self.beam_width && *count < max_per_bucket {
+                        *count += 1;
+                        beam.push(child);
+                    } else {
+                        pool.push(child.state);
+                    }
+                }
+                buckets.clear();
+            } else {
+                if children.len() > self.beam_width {
+                    // 上位 beam_width 個だけ残す。全体はソートしない
+                    children.select_nth_unstable_by(self.beam_width - 1, |a, b| b.cmp(a));
+                    pool.extend(children.drain(self.beam_width..).map(|child| child.state));
                 }
+                // 入れ替えれば children は空になる
+                mem::swap(&mut beam, &mut children);
+            }
+            positions.clear();
+            stats.max_depth += 1;
+            let Some(candidate) = beam.iter().max() else {
+                break;
+            };
+            best = candidate.index;
+            if candidate.state.done() {
+                break;
             }
         }
-        best_action.unwrap()
+        stats.elapsed = time_keeper.elapsed();
+        restore_actions(&tree, best)
+    }
+}
+
+impl<S> ChooseAction<S> for Beam
+where
+    S: OnePlayerGameState,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let mut stats = SearchStats::default();
+        let actions = self.search(state, &mut stats);
+        (actions[0], stats)
+    }
+}
+
+impl<S> PlanActions<S> for Beam
+where
+    S: OnePlayerGameState,
+{
+    fn plan(&self, state: &S) -> Vec<S::Action> {
+        self.search(state, &mut SearchStats::default())
     }
 }
@@ -1,11 +1,25 @@
-use ::std::{collections::BinaryHeap, time::Duration};
+use ::std::{
+    collections::{BinaryHeap, HashSet},
+    time::Duration,
+};
 
-use crate::{game::one_player::OnePlayerGameState, search::one_player::ChooseAction, TimeKeeper};
+use crate::{
+    game::one_player::OnePlayerGameState,
+    search::{
+        one_player::{restore_actions, Candidate, ChooseAction, PlanActions},
+        SearchStats,
+    },
+    TimeKeeper,
+};
 
 pub struct Chokudai {
     beam_width: usize,
     beam_depth: usize,
     threshold: Duration,
+    dedup: bool,
+    // 1周ごとに beam_width を width_step ずつ max_width まで広げる
+    width_step: usize,
+    max_width: usize,
 }
 
 impl Chokudai {
@@ -14,47 +28,131 @@ impl Chokudai {
             beam_width,
             beam_depth,
             threshold,
+            dedup: false,
+            width_step: 0,
+            max_width: beam_width,
         }
     }
-}
 
-impl<S> ChooseAction<S> for Chokudai
-where
-    S: OnePlayerGameState + Ord,
-    S::Action: Ord,
-{
-    fn choose(&self, state: &S) -> S::Action {
+    // Beam::with_dedup と同じ
+    // 一度展開した状態は、以降の周回でも同じ深さでは展開しない
+    pub fn with_dedup(mut self) -> Self {
+        self.dedup = true;
+        self
+    }
+
+    // 最初は狭く探索してすぐにそれなりの解を見つけ、時間が経つにつれて広く探索する
+    pub fn with_widening(mut self, width_step: usize, max_width: usize) -> Self {
+        assert!(self.beam_width <= max_width);
+        self.width_step = width_step;
+        self.max_width = max_width;
+        self
+    }
+
+    // 終了状態に着いていれば evaluate_score() が一番よい終了状態への手順を、
+    // 着いていなければ一番深いところにある一番よい状態への手順を返す
+    // 1手も展開できなければ空
+    fn search<S>(&self, state: &S, stats: &mut SearchStats<S::Action>) -> Vec<S::Action>
+    where
+        S: OnePlayerGameState,
+    {
         let time_keeper = TimeKeeper::new(self.threshold);
+        // Beam と同じく手だけを木で持つ
+        let mut tree = Vec::new();
         let mut heaps = vec![BinaryHeap::new(); self.beam_depth + 1];
-        heaps[0].push((state.clone(), None));
+        heaps[0].push(Candidate::new(state.clone(), None));
+        let mut seen = vec![HashSet::new(); self.beam_depth + 1];
+        // (evaluate_score(), tree での添字)
+        let mut best_done: Option<(u32, usize)> = None;
+        let mut beam_width = self.beam_width;
         'outer: loop {
+            let mut progressed = false;
             for t in 0..self.beam_depth {
-                for _ in 0..self.beam_width {
+                let mut expanded = 0;
+                while expanded < beam_width {
                     if time_keeper.time_over() {
                         break 'outer;
                     }
-                    let Some((now_state, first_action)) = heaps[t].pop() else {
+                    let Some(candidate) = heaps[t].pop() else {
                         break;
                     };
-                    if now_state.done() {
+                    if candidate.state.done() {
                         // 戻す
-                        heaps[t].push((now_state, first_action));
+                        heaps[t].push(candidate);
                         break;
                     }
+                    let Candidate {
+                        state: now_state,
+                        index,
+                        ..
+                    } = candidate;
+                    if self.dedup && !seen[t].insert(now_state.state_hash()) {
+                        continue;
+                    }
+                    expanded += 1;
+                    progressed = true;
                     let legal_actions = now_state.legal_actions();
                     for action in legal_actions {
                         let mut next_state = now_state.clone();
                         next_state.advance(action);
-                        heaps[t + 1].push((next_state, first_action.or(Some(action))));
+                        stats.nodes += 1;
+                        stats.max_depth = stats.max_depth.max(t as u32 + 1);
+                        tree.push((index, action));
+                        if next_state.done() {
+                            let score = next_state.evaluate_score();
+                            if best_done.map_or(true, |(best_score, _)| best_score < score) {
+                                best_done = Some((score, tree.len() - 1));
+                            }
+                        }
+                        heaps[t + 1].push(Candidate::new(next_state, Some(tree.len() - 1)));
                     }
                 }
             }
+            if !progressed {
+                // 全部の heap が空か終了状態なので、これ以上探索しても変わらない
+                break;
+            }
+            beam_width = (beam_width + self.width_step).min(self.max_width);
+        }
+        stats.elapsed = time_keeper.elapsed();
+        if let Some((_, index)) = best_done {
+            return restore_actions(&tree, Some(index));
         }
-        for t in (0..=self.beam_depth).rev() {
-            if let Some((_, action)) = heaps[t].peek() {
-                return action.unwrap();
+        for t in (1..=self.beam_depth).rev() {
+            if let Some(candidate) = heaps[t].peek() {
+                return restore_actions(&tree, candidate.index);
             }
         }
-        unimplemented!("no action")
+        Vec::new()
+    }
+}
+
+impl<S> ChooseAction<S> for Chokudai
+where
+    S: OnePlayerGameState,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let mut stats = SearchStats::default();
+        let actions = self.search(state, &mut stats);
+        // 時間が短すぎて1手も展開できなかったときは合法手のどれかを返す
+        let action = actions
+            .first()
+            .copied()
+            .or_else(|| state.legal_actions().first().copied())
+            .unwrap_or_else(|| panic!("合法手がない"));
+        (action, stats)
+    }
+}
+
+impl<S> PlanActions<S> for Chokudai
+where
+    S: OnePlayerGameState,
+{
+    fn plan(&self, state: &S) -> Vec<S::Action> {
+        self.search(state, &mut SearchStats::default())
     }
 }
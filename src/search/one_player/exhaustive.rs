@@ -0,0 +1,109 @@
+use ::std::time::Instant;
+
+use crate::{
+    game::one_player::OnePlayerGameState,
+    search::{
+        one_player::{ChooseAction, PlanActions},
+        SearchStats,
+    },
+};
+
+// 深さ優先の分枝限定法
+// upper_bound() がそれまでの最良以下の枝は探索しない
+// 時間制限はないので小さい問題の最適解を求めるために使う
+pub struct Exhaustive {}
+
+impl Exhaustive {
+    // (最適な evaluate_score(), その手順) を返す
+    pub fn solve<S>(&self, state: &S) -> (u32, Vec<S::Action>)
+    where
+        S: OnePlayerGameState,
+    {
+        self.solve_with_stats(state, &mut SearchStats::default())
+    }
+
+    fn solve_with_stats<S>(
+        &self,
+        state: &S,
+        stats: &mut SearchStats<S::Action>,
+    ) -> (u32, Vec<S::Action>)
+    where
+        S: OnePlayerGameState,
+    {
+        let mut best = None;
+        dfs(state, &mut Vec::new(), &mut best, stats);
+        // 終了状態に着けなければ今の状態のまま
+        best.unwrap_or_else(|| (state.evaluate_score(), Vec::new()))
+    }
+}
+
+fn dfs<S>(
+    state: &S,
+    actions: &mut Vec<S::Action>,
+    best: &mut Option<(u32, Vec<S::Action>)>,
+    stats: &mut SearchStats<S::Action>,
+) where
+    S: OnePlayerGameState,
+{
+    if state.done() {
+        let score = state.evaluate_score();
+        if best
+            .as_ref()
+            .map_or(true, |(best_score, _)| *best_score < score)
+        {
+            *best = Some((score, actions.clone()));
+        }
+        return;
+    }
+    // 上界が大きい子から探索すると早くよい解が見つかって枝刈りが効く
+    let mut children = state
+        .legal_actions()
+        .into_iter()
+        .map(|action| {
+            let mut next_state = state.clone();
+            next_state.advance(action);
+            stats.nodes += 1;
+            (next_state.upper_bound(), action, next_state)
+        })
+        .collect::<Vec<_>>();
+    children.sort_by(|a, b| b.0.cmp(&a.0));
+    for (bound, action, next_state) in children {
+        if best
+            .as_ref()
+            .is_some_and(|(best_score, _)| bound <= *best_score)
+        {
+            // 並べてあるので残りも全部枝刈りできる
+            break;
+        }
+        actions.push(action);
+        stats.max_depth = stats.max_depth.max(actions.len() as u32);
+        dfs(&next_state, actions, best, stats);
+        actions.pop();
+    }
+}
+
+impl<S> ChooseAction<S> for Exhaustive
+where
+    S: OnePlayerGameState,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let instant = Instant::now();
+        let mut stats = SearchStats::default();
+        let (_, actions) = self.solve_with_stats(state, &mut stats);
+        stats.elapsed = instant.elapsed();
+        (actions[0], stats)
+    }
+}
+
+impl<S> PlanActions<S> for Exhaustive
+where
+    S: OnePlayerGameState,
+{
+    fn plan(&self, state: &S) -> Vec<S::Action> {
+        self.solve(state).1
+    }
+}
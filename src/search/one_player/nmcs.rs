@@ -0,0 +1,123 @@
+use ::std::time::Instant;
+
+use ::rand::{seq::SliceRandom, thread_rng, Rng};
+
+use crate::{
+    game::one_player::OnePlayerGameState,
+    search::{
+        one_player::{ChooseAction, PlanActions},
+        SearchStats,
+    },
+};
+
+// Nested Monte Carlo Search
+// レベル n では、各合法手について レベル n-1 の探索をして、それまでで一番よかった手順に沿って1手進める
+// レベル 0 はランダムプレイアウト
+pub struct NestedMonteCarlo {
+    level: u32,
+}
+
+impl NestedMonteCarlo {
+    pub fn new(level: u32) -> Self {
+        Self { level }
+    }
+
+    fn search<S>(&self, state: &S, stats: &mut SearchStats<S::Action>) -> Vec<S::Action>
+    where
+        S: OnePlayerGameState,
+    {
+        let instant = Instant::now();
+        let mut rng = thread_rng();
+        let (_, actions) = nested(state, self.level, &mut rng, stats);
+        stats.elapsed = instant.elapsed();
+        actions
+    }
+}
+
+// (終了時の evaluate_score(), state からの手順)
+fn nested<S, R>(
+    state: &S,
+    level: u32,
+    rng: &mut R,
+    stats: &mut SearchStats<S::Action>,
+) -> (u32, Vec<S::Action>)
+where
+    S: OnePlayerGameState,
+    R: Rng,
+{
+    if level == 0 {
+        return playout(state, rng, stats);
+    }
+    let mut state = state.clone();
+    let mut best_score = None;
+    let mut best_actions = Vec::new();
+    let mut played = Vec::new();
+    while !state.done() {
+        for action in state.legal_actions() {
+            let mut next_state = state.clone();
+            next_state.advance(action);
+            stats.nodes += 1;
+            let (score, actions) = nested(&next_state, level - 1, rng, stats);
+            if best_score.map_or(true, |best_score| best_score < score) {
+                best_score = Some(score);
+                best_actions.clone_from(&played);
+                best_actions.push(action);
+                best_actions.extend(actions);
+            }
+        }
+        // 合法手がなかった
+        let Some(&action) = best_actions.get(played.len()) else {
+            break;
+        };
+        state.advance(action);
+        played.push(action);
+    }
+    (state.evaluate_score(), played)
+}
+
+fn playout<S, R>(
+    state: &S,
+    rng: &mut R,
+    stats: &mut SearchStats<S::Action>,
+) -> (u32, Vec<S::Action>)
+where
+    S: OnePlayerGameState,
+    R: Rng,
+{
+    stats.playouts += 1;
+    let mut state = state.clone();
+    let mut actions = Vec::new();
+    while !state.done() {
+        let Some(&action) = state.legal_actions().choose(rng) else {
+            break;
+        };
+        state.advance(action);
+        stats.nodes += 1;
+        actions.push(action);
+    }
+    (state.evaluate_score(), actions)
+}
+
+impl<S> ChooseAction<S> for NestedMonteCarlo
+where
+    S: OnePlayerGameState,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let mut stats = SearchStats::default();
+        let actions = self.search(state, &mut stats);
+        (actions[0], stats)
+    }
+}
+
+impl<S> PlanActions<S> for NestedMonteCarlo
+where
+    S: OnePlayerGameState,
+{
+    fn plan(&self, state: &S) -> Vec<S::Action> {
+        self.search(state, &mut SearchStats::default())
+    }
+}
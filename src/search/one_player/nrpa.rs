@@ -0,0 +1,177 @@
+use ::std::{collections::HashMap, time::Instant};
+
+use ::rand::{thread_rng, Rng};
+
+use crate::{
+    game::one_player::OnePlayerGameState,
+    search::{
+        one_player::{ChooseAction, PlanActions},
+        SearchStats,
+    },
+};
+
+// Nested Rollout Policy Adaptation
+// action_key() ごとの重みを方策として持ち、レベル n では レベル n-1 の探索を iterations 回して
+// そのたびにそれまでで一番よかった手順を選びやすくなるように方策を更新する
+// レベル 0 は方策にしたがうプレイアウト
+pub struct NRPA {
+    level: u32,
+    iterations: u32,
+    alpha: f64,
+}
+
+impl NRPA {
+    pub fn new(level: u32, iterations: u32) -> Self {
+        Self {
+            level,
+            iterations,
+            alpha: 1.0,
+        }
+    }
+
+    // 方策を更新するときの学習率
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        assert!(alpha > 0.0);
+        self.alpha = alpha;
+        self
+    }
+
+    fn search<S>(&self, state: &S, stats: &mut SearchStats<S::Action>) -> Vec<S::Action>
+    where
+        S: OnePlayerGameState,
+    {
+        let instant = Instant::now();
+        let mut rng = thread_rng();
+        let (_, actions) = self.nested(state, self.level, &HashMap::new(), &mut rng, stats);
+        stats.elapsed = instant.elapsed();
+        actions
+    }
+
+    // (終了時の evaluate_score(), state からの手順)
+    fn nested<S, R>(
+        &self,
+        state: &S,
+        level: u32,
+        policy: &HashMap<u64, f64>,
+        rng: &mut R,
+        stats: &mut SearchStats<S::Action>,
+    ) -> (u32, Vec<S::Action>)
+    where
+        S: OnePlayerGameState,
+        R: Rng,
+    {
+        if level == 0 {
+            return playout(state, policy, rng, stats);
+        }
+        let mut policy = policy.clone();
+        let mut best: Option<(u32, Vec<S::Action>)> = None;
+        for _ in 0..self.iterations {
+            let (score, actions) = self.nested(state, level - 1, &policy, rng, stats);
+            // 同点なら新しいほうにする
+            if best
+                .as_ref()
+                .map_or(true, |(best_score, _)| *best_score <= score)
+            {
+                best = Some((score, actions));
+            }
+            if let Some((_, best_actions)) = &best {
+                self.adapt(state, best_actions, &mut policy);
+            }
+        }
+        best.unwrap_or_else(|| (state.evaluate_score(), Vec::new()))
+    }
+
+    // actions の各手の選ばれる確率が上がるように policy を更新する
+    fn adapt<S>(&self, state: &S, actions: &[S::Action], policy: &mut HashMap<u64, f64>)
+    where
+        S: OnePlayerGameState,
+    {
+        // 更新前の方策で確率を計算する
+        let old_policy = policy.clone();
+        let mut state = state.clone();
+        for &action in actions {
+            let legal_actions = state.legal_actions();
+            let weights = weights(&state, &legal_actions, &old_policy);
+            let total = weights.iter().sum::<f64>();
+            *policy.entry(state.action_key(action)).or_insert(0.0) += self.alpha;
+            for (&a, w) in legal_actions.iter().zip(weights) {
+                *policy.entry(state.action_key(a)).or_insert(0.0) -= self.alpha * w / total;
+            }
+            state.advance(action);
+        }
+    }
+}
+
+// exp(方策の重み)
+fn weights<S>(state: &S, legal_actions: &[S::Action], policy: &HashMap<u64, f64>) -> Vec<f64>
+where
+    S: OnePlayerGameState,
+{
+    legal_actions
+        .iter()
+        .map(|&action| {
+            let key = state.action_key(action);
+            policy.get(&key).copied().unwrap_or(0.0).exp()
+        })
+        .collect()
+}
+
+fn playout<S, R>(
+    state: &S,
+    policy: &HashMap<u64, f64>,
+    rng: &mut R,
+    stats: &mut SearchStats<S::Action>,
+) -> (u32, Vec<S::Action>)
+where
+    S: OnePlayerGameState,
+    R: Rng,
+{
+    stats.playouts += 1;
+    let mut state = state.clone();
+    let mut actions = Vec::new();
+    while !state.done() {
+        let legal_actions = state.legal_actions();
+        if legal_actions.is_empty() {
+            break;
+        }
+        let weights = weights(&state, &legal_actions, policy);
+        let mut r = rng.gen::<f64>() * weights.iter().sum::<f64>();
+        // 誤差で最後まで r が残ったら最後の手にする
+        let mut action = legal_actions[legal_actions.len() - 1];
+        for (&a, w) in legal_actions.iter().zip(weights) {
+            if r < w {
+                action = a;
+                break;
+            }
+            r -= w;
+        }
+        state.advance(action);
+        stats.nodes += 1;
+        actions.push(action);
+    }
+    (state.evaluate_score(), actions)
+}
+
+impl<S> ChooseAction<S> for NRPA
+where
+    S: OnePlayerGameState,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let mut stats = SearchStats::default();
+        let actions = self.search(state, &mut stats);
+        (actions[0], stats)
+    }
+}
+
+impl<S> PlanActions<S> for NRPA
+where
+    S: OnePlayerGameState,
+{
+    fn plan(&self, state: &S) -> Vec<S::Action> {
+        self.search(state, &mut SearchStats::default())
+    }
+}
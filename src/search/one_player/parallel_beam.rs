@@ -0,0 +1,172 @@
+use ::std::{cmp::Ordering, thread, time::Duration};
+
+use crate::{
+    game::one_player::OnePlayerGameState,
+    search::{
+        one_player::{restore_actions, ChooseAction, PlanActions},
+        SearchStats,
+    },
+    TimeKeeper,
+};
+
+// 各深さのビームを threads 個に分けて並列に展開する
+// 同点の順位をスレッド数によらない生成順で決めるので、時間切れにならなければ結果は毎回同じ
+// 時間切れは深さごとにしか確認しない
+pub struct ParallelBeam {
+    beam_width: usize,
+    threads: usize,
+    threshold: Duration,
+}
+
+impl ParallelBeam {
+    pub fn new(beam_width: usize, threads: usize, threshold: Duration) -> Self {
+        assert!(beam_width > 0);
+        assert!(threads > 0);
+        Self {
+            beam_width,
+            threads,
+            threshold,
+        }
+    }
+
+    // Beam と同じく、最後まで探索できた深さで一番よい状態への手順を返す
+    fn search<S>(&self, state: &S, stats: &mut SearchStats<S::Action>) -> Vec<S::Action>
+    where
+        S: OnePlayerGameState + Send + Sync,
+        S::Action: Send,
+    {
+        let time_keeper = TimeKeeper::new(self.threshold);
+        let mut tree = Vec::new();
+        let mut best = None;
+        // (状態, tree での添字)
+        let mut beam = vec![(state.clone(), None)];
+        while !time_keeper.time_over() {
+            let chunk_size = beam.len().div_ceil(self.threads);
+            let (mut children, generated) = thread::scope(|scope| {
+                let handles = beam
+                    .chunks(chunk_size)
+                    .enumerate()
+                    .map(|(i, chunk)| {
+                        scope.spawn(move || expand(chunk, i * chunk_size, self.beam_width))
+                    })
+                    .collect::<Vec<_>>();
+                // スレッドごとの上位をスレッドの順につなげる
+                let mut children = Vec::new();
+                let mut generated = 0;
+                for handle in handles {
+                    let (local_children, local_generated) = handle.join().unwrap();
+                    children.extend(local_children);
+                    generated += local_generated;
+                }
+                (children, generated)
+            });
+            stats.nodes += generated;
+            select_top(&mut children, self.beam_width);
+            // 生成順に並べなおして tree に追加する
+            children.sort_unstable_by_key(|child| child.order);
+            let Some(top) = (0..children.len()).max_by(|&i, &j| children[i].cmp(&children[j]))
+            else {
+                break;
+            };
+            best = Some(tree.len() + top);
+            let done = children[top].state.done();
+            beam = children
+                .into_iter()
+                .map(|child| {
+                    tree.push((child.parent, child.action));
+                    (child.state, Some(tree.len() - 1))
+                })
+                .collect();
+            stats.max_depth += 1;
+            if done {
+                break;
+            }
+        }
+        stats.elapsed = time_keeper.elapsed();
+        restore_actions(&tree, best)
+    }
+}
+
+impl<S> ChooseAction<S> for ParallelBeam
+where
+    S: OnePlayerGameState + Send + Sync,
+    S::Action: Send,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let mut stats = SearchStats::default();
+        let actions = self.search(state, &mut stats);
+        (actions[0], stats)
+    }
+}
+
+impl<S> PlanActions<S> for ParallelBeam
+where
+    S: OnePlayerGameState + Send + Sync,
+    S::Action: Send,
+{
+    fn plan(&self, state: &S) -> Vec<S::Action> {
+        self.search(state, &mut SearchStats::default())
+    }
+}
+
+struct Child<S>
+where
+    S: OnePlayerGameState,
+{
+    score: i64,
+    // (親のビーム内の位置, 合法手の中での位置)
+    order: (usize, usize),
+    parent: Option<usize>,
+    action: S::Action,
+    state: S,
+}
+
+impl<S> Child<S>
+where
+    S: OnePlayerGameState,
+{
+    // heuristic_score() が大きいほうが大きく、同点なら先に生成したほうが大きい
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .cmp(&other.score)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+// beam[offset..] の一部である chunk を展開して、(その中の上位 beam_width 個, 生成した数) を返す
+fn expand<S>(chunk: &[(S, Option<usize>)], offset: usize, beam_width: usize) -> (Vec<Child<S>>, u64)
+where
+    S: OnePlayerGameState,
+{
+    let mut children = Vec::new();
+    for (i, (now_state, index)) in chunk.iter().enumerate() {
+        for (j, action) in now_state.legal_actions().into_iter().enumerate() {
+            let mut next_state = now_state.clone();
+            next_state.advance(action);
+            children.push(Child {
+                score: next_state.heuristic_score(),
+                order: (offset + i, j),
+                parent: *index,
+                action,
+                state: next_state,
+            });
+        }
+    }
+    let generated = children.len() as u64;
+    select_top(&mut children, beam_width);
+    (children, generated)
+}
+
+fn select_top<S>(children: &mut Vec<Child<S>>, beam_width: usize)
+where
+    S: OnePlayerGameState,
+{
+    if children.len() > beam_width {
+        children.select_nth_unstable_by(beam_width - 1, |a, b| b.cmp(a));
+        children.truncate(beam_width);
+    }
+}
@@ -0,0 +1,248 @@
+use ::std::time::Duration;
+
+use ::rand::{seq::SliceRandom, thread_rng, Rng};
+
+use crate::{
+    game::one_player::OnePlayerGameState,
+    search::{
+        one_player::{ChooseAction, PlanActions},
+        RootActionStats, SearchStats,
+    },
+    TimeKeeper,
+};
+
+// Single-Player MCTS
+// 報酬は evaluate_score() をそれまでの最高スコアで割って [0, 1] にしたもの
+// UCB1 に報酬の分散の項を足して子を選び、最後は平均ではなく見つけた中で最高スコアの手順を返す
+#[allow(clippy::upper_case_acronyms)]
+pub struct SPMCTS {
+    threshold: Duration,
+    c: f64,
+    d: f64,
+}
+
+impl SPMCTS {
+    pub fn new(threshold: Duration) -> Self {
+        Self {
+            threshold,
+            // 報酬を正規化しているので、論文の値 (C = 0.5, D = 10000) よりずっと小さくする
+            c: 0.1,
+            d: 0.001,
+        }
+    }
+
+    // c: 探索の項の係数, d: 分散の項に足す定数。試行回数が少ないノードほど大きく効く
+    pub fn with_constants(mut self, c: f64, d: f64) -> Self {
+        assert!(c >= 0.0);
+        assert!(d >= 0.0);
+        self.c = c;
+        self.d = d;
+        self
+    }
+
+    // (探索後のルート, 最高スコアとその手順, 反復回数)
+    fn search<S>(
+        &self,
+        state: &S,
+        time_keeper: &TimeKeeper,
+    ) -> (Node<S>, (u32, Vec<S::Action>), u64)
+    where
+        S: OnePlayerGameState,
+    {
+        let mut rng = thread_rng();
+        let mut root = Node::new(state.clone());
+        root.expand();
+        let mut best = (0, Vec::new());
+        let mut iterations = 0;
+        while !time_keeper.time_over() {
+            // 最高スコアが 0 のうちは報酬を全部 0 とみなす
+            let scale = f64::from(best.0.max(1));
+            let (score, actions) = root.evaluate(self, scale, &mut rng);
+            if iterations == 0 || best.0 < score {
+                best = (score, actions);
+            }
+            iterations += 1;
+        }
+        (root, best, iterations)
+    }
+}
+
+impl<S> ChooseAction<S> for SPMCTS
+where
+    S: OnePlayerGameState,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let time_keeper = TimeKeeper::new(self.threshold);
+        let mut stats = SearchStats::default();
+        let (root, (best_score, best_actions), iterations) = self.search(state, &time_keeper);
+        stats.playouts = iterations;
+        (stats.nodes, stats.max_depth) = root.size_and_depth();
+        stats.elapsed = time_keeper.elapsed();
+        let scale = f64::from(best_score.max(1));
+        stats.root_actions = state
+            .legal_actions()
+            .into_iter()
+            .zip(&root.child_nodes)
+            .map(|(action, node)| RootActionStats {
+                action,
+                visits: u64::from(node.attempt),
+                value: node.mean() / scale,
+            })
+            .collect();
+        // 1回も反復できなかったときは最初の合法手
+        let action = best_actions
+            .first()
+            .copied()
+            .or_else(|| state.legal_actions().first().copied())
+            .unwrap_or_else(|| panic!("合法手がない"));
+        (action, stats)
+    }
+}
+
+impl<S> PlanActions<S> for SPMCTS
+where
+    S: OnePlayerGameState,
+{
+    fn plan(&self, state: &S) -> Vec<S::Action> {
+        let (_, (_, actions), _) = self.search(state, &TimeKeeper::new(self.threshold));
+        actions
+    }
+}
+
+struct Node<S> {
+    state: S,
+    attempt: u32,
+    // 報酬を正規化する前のスコアの和と二乗和
+    sum: f64,
+    sum_sq: f64,
+    child_nodes: Vec<Node<S>>,
+}
+
+impl<S> Node<S>
+where
+    S: OnePlayerGameState,
+{
+    fn new(state: S) -> Self {
+        Self {
+            state,
+            attempt: 0,
+            sum: 0.0,
+            sum_sq: 0.0,
+            child_nodes: Vec::new(),
+        }
+    }
+
+    fn mean(&self) -> f64 {
+        if self.attempt == 0 {
+            0.0
+        } else {
+            self.sum / f64::from(self.attempt)
+        }
+    }
+
+    // (部分木のノード数, 部分木の深さ)
+    fn size_and_depth(&self) -> (u64, u32) {
+        self.child_nodes.iter().fold((1, 0), |(size, depth), node| {
+            let (s, d) = node.size_and_depth();
+            (size + s, depth.max(d + 1))
+        })
+    }
+
+    fn expand(&mut self) {
+        assert!(self.child_nodes.is_empty());
+        for action in self.state.legal_actions() {
+            let mut next_state = self.state.clone();
+            next_state.advance(action);
+            self.child_nodes.push(Node::new(next_state));
+        }
+    }
+
+    fn update(&mut self, score: u32) {
+        let score = f64::from(score);
+        self.sum += score;
+        self.sum_sq += score * score;
+        self.attempt += 1;
+    }
+
+    // (終了時のスコア, self からの手順)
+    fn evaluate<R>(&mut self, search: &SPMCTS, scale: f64, rng: &mut R) -> (u32, Vec<S::Action>)
+    where
+        R: Rng,
+    {
+        if self.state.done() {
+            let score = self.state.evaluate_score();
+            self.update(score);
+            (score, Vec::new())
+        } else if self.child_nodes.is_empty() {
+            const EXPAND_THRESHOLD: u32 = 10;
+            let (score, actions) = playout(&self.state, rng);
+            self.update(score);
+            if self.attempt == EXPAND_THRESHOLD {
+                self.expand();
+            }
+            (score, actions)
+        } else {
+            fn ucb<T>(child: &Node<T>, t: u32, search: &SPMCTS, scale: f64) -> f64
+            where
+                T: OnePlayerGameState,
+            {
+                assert_ne!(child.attempt, 0);
+                let attempt = f64::from(child.attempt);
+                let mean = child.sum / scale / attempt;
+                // 正規化した報酬の分散
+                let variance = (child.sum_sq / (scale * scale) / attempt - mean * mean).max(0.0);
+                mean + search.c * f64::sqrt(2.0 * f64::from(t).ln() / attempt)
+                    + f64::sqrt(variance + search.d / attempt)
+            }
+            let index = 'next_child_node_index: {
+                for (i, node) in self.child_nodes.iter().enumerate() {
+                    // 一度も探索していないノードは最優先
+                    if node.attempt == 0 {
+                        break 'next_child_node_index i;
+                    }
+                }
+                let t = self
+                    .child_nodes
+                    .iter()
+                    .map(|node| node.attempt)
+                    .sum::<u32>();
+                (0..self.child_nodes.len())
+                    .max_by(|&i, &j| {
+                        let left = ucb(&self.child_nodes[i], t, search, scale);
+                        let right = ucb(&self.child_nodes[j], t, search, scale);
+                        left.total_cmp(&right)
+                    })
+                    .unwrap()
+            };
+            // expand() と同じく legal_actions() の順に子ノードが並んでいる
+            let action = self.state.legal_actions()[index];
+            let (score, actions) = self.child_nodes[index].evaluate(search, scale, rng);
+            self.update(score);
+            let mut path = vec![action];
+            path.extend(actions);
+            (score, path)
+        }
+    }
+}
+
+// 終了までランダムに進めて (終了時のスコア, 手順) を返す
+fn playout<S, R>(state: &S, rng: &mut R) -> (u32, Vec<S::Action>)
+where
+    S: OnePlayerGameState,
+    R: Rng,
+{
+    let mut state = state.clone();
+    let mut actions = Vec::new();
+    while !state.done() {
+        let Some(&action) = state.legal_actions().choose(rng) else {
+            break;
+        };
+        state.advance(action);
+        actions.push(action);
+    }
+    (state.evaluate_score(), actions)
+}
@@ -0,0 +1,235 @@
+use ::std::{cmp::Ordering, mem, time::Duration};
+
+use crate::{
+    game::one_player::OnePlayerGameState,
+    search::{
+        one_player::{restore_actions, ChooseAction, PlanActions},
+        SearchStats,
+    },
+    TimeKeeper,
+};
+
+// 差分更新ビームサーチ
+// 状態を clone() せず、1つの状態を advance() と undo() で手の木に沿って動かして葉を展開する
+pub struct TreeBeam {
+    beam_width: usize,
+    threshold: Duration,
+}
+
+impl TreeBeam {
+    pub fn new(beam_width: usize, threshold: Duration) -> Self {
+        assert!(beam_width > 0);
+        Self {
+            beam_width,
+            threshold,
+        }
+    }
+
+    // Beam と同じく、最後まで探索できた深さで一番よい状態への手順を返す
+    fn search<S>(&self, state: &S, stats: &mut SearchStats<S::Action>) -> Vec<S::Action>
+    where
+        S: OnePlayerGameState,
+    {
+        let time_keeper = TimeKeeper::new(self.threshold);
+        let mut tree = Tree {
+            nodes: Vec::new(),
+            children: Vec::new(),
+            root_children: Vec::new(),
+        };
+        let mut best = None;
+        let mut state = state.clone();
+        let mut leaves = Vec::new();
+        // 葉の深さ。ルートが 0
+        for depth in 0.. {
+            leaves.clear();
+            let completed = if depth == 0 {
+                expand(&mut state, None, &mut leaves, &time_keeper)
+            } else {
+                tree.walk(
+                    &tree.root_children,
+                    depth - 1,
+                    &mut state,
+                    &mut leaves,
+                    &time_keeper,
+                )
+            };
+            if !completed || leaves.is_empty() {
+                break;
+            }
+            stats.nodes += leaves.len() as u64;
+            if leaves.len() > self.beam_width {
+                // Beam と同じく上位 beam_width 個だけ残す
+                leaves.select_nth_unstable_by(self.beam_width - 1, |a, b| b.cmp(a));
+                leaves.truncate(self.beam_width);
+            }
+            let top = (0..leaves.len())
+                .max_by(|&i, &j| leaves[i].cmp(&leaves[j]))
+                .unwrap();
+            // 葉は leaves の順に木に追加される
+            best = Some(tree.nodes.len() + top);
+            let done = leaves[top].done;
+            for leaf in &leaves {
+                tree.push(leaf.parent, leaf.action);
+            }
+            tree.prune(depth + 1);
+            stats.max_depth = depth + 1;
+            if done {
+                break;
+            }
+        }
+        stats.elapsed = time_keeper.elapsed();
+        restore_actions(&tree.nodes, best)
+    }
+}
+
+impl<S> ChooseAction<S> for TreeBeam
+where
+    S: OnePlayerGameState,
+{
+    fn choose(&self, state: &S) -> S::Action {
+        self.choose_with_stats(state).0
+    }
+
+    fn choose_with_stats(&self, state: &S) -> (S::Action, SearchStats<S::Action>) {
+        let mut stats = SearchStats::default();
+        let actions = self.search(state, &mut stats);
+        (actions[0], stats)
+    }
+}
+
+impl<S> PlanActions<S> for TreeBeam
+where
+    S: OnePlayerGameState,
+{
+    fn plan(&self, state: &S) -> Vec<S::Action> {
+        self.search(state, &mut SearchStats::default())
+    }
+}
+
+// 状態を持たない展開候補
+struct Leaf<A> {
+    score: i64,
+    // 生成した順番。同点のときに先に生成したほうを優先する
+    order: usize,
+    parent: Option<usize>,
+    action: A,
+    done: bool,
+}
+
+impl<A> Ord for Leaf<A> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .cmp(&other.score)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl<A> PartialOrd for Leaf<A> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<A> PartialEq for Leaf<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.order == other.order
+    }
+}
+
+impl<A> Eq for Leaf<A> {}
+
+// state の合法手を全部試して leaves に入れ、state は元に戻す
+// 時間切れなら false
+fn expand<S>(
+    state: &mut S,
+    parent: Option<usize>,
+    leaves: &mut Vec<Leaf<S::Action>>,
+    time_keeper: &TimeKeeper,
+) -> bool
+where
+    S: OnePlayerGameState,
+{
+    if time_keeper.time_over() {
+        return false;
+    }
+    for action in state.legal_actions() {
+        state.advance(action);
+        leaves.push(Leaf {
+            score: state.heuristic_score(),
+            order: leaves.len(),
+            parent,
+            action,
+            done: state.done(),
+        });
+        state.undo(action);
+    }
+    true
+}
+
+struct Tree<A> {
+    // restore_actions() に渡せる形で (親, 手) を持つ
+    nodes: Vec<(Option<usize>, A)>,
+    children: Vec<Vec<usize>>,
+    root_children: Vec<usize>,
+}
+
+impl<A> Tree<A>
+where
+    A: Copy,
+{
+    fn push(&mut self, parent: Option<usize>, action: A) {
+        let index = self.nodes.len();
+        self.nodes.push((parent, action));
+        self.children.push(Vec::new());
+        match parent {
+            Some(p) => self.children[p].push(index),
+            None => self.root_children.push(index),
+        }
+    }
+
+    // ids の各ノードへ進み、rest 手先の葉を展開する
+    fn walk<S>(
+        &self,
+        ids: &[usize],
+        rest: u32,
+        state: &mut S,
+        leaves: &mut Vec<Leaf<A>>,
+        time_keeper: &TimeKeeper,
+    ) -> bool
+    where
+        S: OnePlayerGameState<Action = A>,
+    {
+        for &id in ids {
+            let (_, action) = self.nodes[id];
+            state.advance(action);
+            let completed = if rest == 0 {
+                expand(state, Some(id), leaves, time_keeper)
+            } else {
+                self.walk(&self.children[id], rest - 1, state, leaves, time_keeper)
+            };
+            state.undo(action);
+            if !completed {
+                return false;
+            }
+        }
+        true
+    }
+
+    // 深さ depth の葉につながらない枝を木から外す
+    fn prune(&mut self, depth: u32) {
+        let mut root_children = mem::take(&mut self.root_children);
+        root_children.retain(|&id| self.alive(id, depth - 1));
+        self.root_children = root_children;
+    }
+
+    fn alive(&mut self, id: usize, rest: u32) -> bool {
+        if rest == 0 {
+            return true;
+        }
+        let mut children = mem::take(&mut self.children[id]);
+        children.retain(|&child| self.alive(child, rest - 1));
+        let alive = !children.is_empty();
+        self.children[id] = children;
+        alive
+    }
+}